@@ -0,0 +1,103 @@
+//! Compares reading a large `BODY` response through `NNTPStream`'s buffered
+//! reader with reading it one byte per `read` call, as the client used to.
+//!
+//! Run with `cargo bench --bench read_path`. A local server thread serves a
+//! ~700 KB body, so the numbers reflect syscall overhead, not the network.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use nntp::NNTPStream;
+
+const ITERATIONS: u32 = 20;
+const BODY_LINES: usize = 10_000;
+
+fn main() {
+    let body = build_body();
+    let addr = spawn_server(body.clone());
+
+    let unbuffered = time(|| {
+        let mut stream = TcpStream::connect(&addr).unwrap();
+        read_line_bytewise(&mut stream);
+        move || {
+            stream.write_all(b"BODY <bench@example.com>\r\n").unwrap();
+            read_line_bytewise(&mut stream);
+            while read_line_bytewise(&mut stream) != b".\r\n" {}
+        }
+    });
+
+    let buffered = time(|| {
+        let mut client = NNTPStream::connect(addr.clone()).unwrap();
+        move || {
+            let lines = client.body_by_id("<bench@example.com>").unwrap();
+            assert_eq!(lines.len(), BODY_LINES);
+        }
+    });
+
+    println!("BODY of {} bytes, {} iterations", body.len(), ITERATIONS);
+    println!("  byte-at-a-time: {:>10.2?} per response", unbuffered);
+    println!("  NNTPStream:     {:>10.2?} per response", buffered);
+}
+
+/// Times `ITERATIONS` runs of the closure returned by `setup`.
+fn time<F: FnMut()>(setup: impl FnOnce() -> F) -> Duration {
+    let mut run = setup();
+    run();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        run();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn read_line_bytewise(stream: &mut TcpStream) -> Vec<u8> {
+    let mut line = Vec::new();
+    while !line.ends_with(b"\r\n") {
+        let mut byte = [0u8; 1];
+        stream.read_exact(&mut byte).unwrap();
+        line.push(byte[0]);
+    }
+    line
+}
+
+fn build_body() -> Vec<u8> {
+    let mut body = Vec::new();
+    for i in 0..BODY_LINES {
+        body.extend_from_slice(format!("{:06} ", i).as_bytes());
+        body.extend_from_slice(&[b'x'; 61]);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(b".\r\n");
+    body
+}
+
+fn spawn_server(body: Vec<u8>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let body = body.clone();
+            thread::spawn(move || serve(stream.unwrap(), &body));
+        }
+    });
+    addr
+}
+
+fn serve(stream: TcpStream, body: &[u8]) {
+    let mut writer = stream.try_clone().unwrap();
+    let mut reader = BufReader::new(stream);
+    writer.write_all(b"200 bench server ready\r\n").unwrap();
+
+    let mut command = String::new();
+    while reader.read_line(&mut command).unwrap_or(0) > 0 {
+        if command.starts_with("BODY") {
+            writer.write_all(b"222 0 <bench@example.com>\r\n").unwrap();
+            writer.write_all(body).unwrap();
+        } else {
+            writer.write_all(b"500 unknown command\r\n").unwrap();
+        }
+        command.clear();
+    }
+}
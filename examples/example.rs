@@ -9,9 +9,9 @@ fn main() {
     };
 
     match nntp_stream.capabilities() {
-        Ok(lines) => {
-            for line in lines.iter() {
-                print!("{}", line);
+        Ok(capabilities) => {
+            for line in capabilities.lines.iter() {
+                println!("{}", line);
             }
         }
         Err(e) => panic!("{}", e),
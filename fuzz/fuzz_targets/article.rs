@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nntp::Article;
+
+fuzz_target!(|text: &str| {
+    let lines: Vec<String> = text.split_inclusive("\r\n").map(String::from).collect();
+
+    let article = Article::new_article(lines.clone());
+    let _ = article.to_string();
+    for (name, _) in &article.headers {
+        let _ = article.headers.get_all(name).count();
+    }
+
+    if let Ok(article) = Article::parse(lines) {
+        let _ = article.to_string();
+    }
+});
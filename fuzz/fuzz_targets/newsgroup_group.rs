@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nntp::NewsGroup;
+
+fuzz_target!(|line: &str| {
+    if let Ok(group) = NewsGroup::from_group_response(line) {
+        let _ = group.to_string();
+    }
+});
@@ -11,7 +11,24 @@ pub struct ServerAddress {
     /// The server port number.
     pub port: u16,
     /// TLS configuration. `None` means plain TCP.
+    /// See [`TlsConfig::starttls`] to upgrade a plain connection instead.
     pub tls: Option<TlsConfig>,
+    /// Enable `COMPRESS DEFLATE` (RFC 8054) whenever the server advertises it.
+    ///
+    /// As authentication is not allowed once compression is active, servers
+    /// that offer `AUTHINFO` are only compressed after authenticating;
+    /// others are compressed on connect.
+    ///
+    /// ```no_run
+    /// use nntp::{NNTPStream, ServerAddress};
+    ///
+    /// let addr = ServerAddress {
+    ///     compress: true,
+    ///     ..ServerAddress::new("nntp.example.com", 119)
+    /// };
+    /// let client = NNTPStream::connect_with(addr);
+    /// ```
+    pub compress: bool,
 }
 
 impl ServerAddress {
@@ -21,6 +38,21 @@ impl ServerAddress {
             host: host.into(),
             port,
             tls: None,
+            compress: false,
+        }
+    }
+
+    /// Creates a server address that connects in plain text and upgrades to
+    /// TLS with `STARTTLS` (RFC 4642) before any authentication.
+    pub fn with_starttls(host: impl Into<String>, port: u16, config: TlsConfig) -> Self {
+        ServerAddress {
+            host: host.into(),
+            port,
+            tls: Some(TlsConfig {
+                starttls: true,
+                ..config
+            }),
+            compress: false,
         }
     }
 
@@ -30,6 +62,7 @@ impl ServerAddress {
             host: host.into(),
             port,
             tls: Some(config),
+            compress: false,
         }
     }
 
@@ -52,6 +85,7 @@ impl ServerAddress {
                 host: host.to_owned(),
                 port,
                 tls: Some(TlsConfig::default()),
+                compress: false,
             })
         } else if let Some(rest) = addr.strip_prefix("nntp://") {
             let (host, port) = parse_host_port(rest, 119)?;
@@ -59,6 +93,7 @@ impl ServerAddress {
                 host: host.to_owned(),
                 port,
                 tls: None,
+                compress: false,
             })
         } else {
             // Bare host:port
@@ -67,11 +102,24 @@ impl ServerAddress {
                 host: host.to_owned(),
                 port,
                 tls: None,
+                compress: false,
             })
         }
     }
 }
 
+impl ServerAddress {
+    /// Returns the TLS configuration if the handshake starts on connect.
+    pub(crate) fn implicit_tls(&self) -> Option<&TlsConfig> {
+        self.tls.as_ref().filter(|tls| !tls.starttls)
+    }
+
+    /// Whether the connection must be upgraded with `STARTTLS`.
+    pub(crate) fn requires_starttls(&self) -> bool {
+        self.tls.as_ref().is_some_and(|tls| tls.starttls)
+    }
+}
+
 fn parse_host_port(s: &str, default_port: u16) -> Result<(&str, u16), &'static str> {
     if let Some((host, port_str)) = s.rsplit_once(':') {
         // Handle IPv6: [::1]:port
@@ -1,10 +1,15 @@
-use std::collections::HashMap;
+use std::fmt;
 use std::string::String;
 use std::vec::Vec;
 
+use crate::date::DateTime;
+use crate::errors::{NNTPError, Result};
+use crate::headers::Headers;
+use crate::message_id::MessageId;
+
 /// A parsed NNTP article.
 ///
-/// Articles consist of a set of headers (key-value pairs) followed by a body.
+/// Articles consist of a set of headers followed by a body.
 /// The [`Article::new_article`] constructor parses raw response lines into this
 /// structured form, splitting on the first blank line (`\r\n`) between headers
 /// and body.
@@ -21,14 +26,14 @@ use std::vec::Vec;
 ///     "Hello world!\r\n".to_string(),
 /// ];
 /// let article = Article::new_article(raw);
-/// assert_eq!(article.headers.get("From"), Some(&" user@example.com".to_string()));
-/// assert_eq!(article.headers.get("Subject"), Some(&" Hello".to_string()));
+/// assert_eq!(article.headers.get("From"), Some("user@example.com"));
+/// assert_eq!(article.headers.get("subject"), Some("Hello"));
 /// assert_eq!(article.body, vec!["Hello world!\r\n".to_string()]);
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Article {
-    /// Article headers as a map of header name to header value.
-    pub headers: HashMap<String, String>,
+    /// Article headers, in the order they were received.
+    pub headers: Headers,
     /// Article body lines (including trailing `\r\n` on each line).
     pub body: Vec<String>,
 }
@@ -36,41 +41,239 @@ pub struct Article {
 impl Article {
     /// Parses raw article lines into an [`Article`].
     ///
-    /// Lines before the first blank line (`\r\n`) are treated as headers.
-    /// Each header line is split on the first `:` to separate the key and value.
-    /// Lines after the blank line are treated as body content.
+    /// Lines before the first blank line (`\r\n`) are treated as headers, see
+    /// [`Headers::from_lines`]. Lines after the blank line are treated as
+    /// body content.
+    ///
+    /// Parsing never fails, so that articles with slightly malformed headers
+    /// can still be read. Use [`Article::parse`] to reject them instead.
     ///
     /// # Arguments
     ///
     /// * `lines` - Raw article lines as returned by the server, including `\r\n` terminators.
-    pub fn new_article(lines: Vec<String>) -> Article {
-        let mut headers = HashMap::new();
-        let mut body = Vec::new();
-        let mut parsing_headers = true;
-
-        for i in lines.iter() {
-            if i == &"\r\n".to_string() {
-                parsing_headers = false;
-                continue;
-            }
-            if parsing_headers {
-                let mut header = i.splitn(2, ':');
-                let chars_to_trim: &[char] = &['\r', '\n'];
-                let key = header
-                    .nth(0)
-                    .unwrap()
-                    .trim_matches(chars_to_trim)
-                    .to_string();
-                let value = header
-                    .nth(0)
-                    .unwrap()
-                    .trim_matches(chars_to_trim)
-                    .to_string();
-                headers.insert(key, value);
-            } else {
-                body.push(i.clone());
-            }
+    pub fn new_article(mut lines: Vec<String>) -> Article {
+        let separator = lines.iter().position(|line| line == "\r\n");
+        let body = match separator {
+            Some(separator) => lines.split_off(separator + 1),
+            None => Vec::new(),
+        };
+        if separator.is_some() {
+            lines.pop();
+        }
+
+        Article {
+            headers: Headers::from_lines(&lines),
+            body,
+        }
+    }
+
+    /// Parses raw article lines into an [`Article`], checking the headers
+    /// with [`Headers::parse`]. Also available as `Article::try_from(lines)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] with the offending line if a header
+    /// line is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nntp::Article;
+    ///
+    /// let raw = vec![
+    ///     "From: user@example.com\r\n".to_string(),
+    ///     "\r\n".to_string(),
+    ///     "Hello world!\r\n".to_string(),
+    /// ];
+    /// assert!(Article::parse(raw).is_ok());
+    ///
+    /// let raw = vec!["not a header\r\n".to_string()];
+    /// assert!(Article::try_from(raw).is_err());
+    /// ```
+    pub fn parse(lines: Vec<String>) -> Result<Article> {
+        let separator = lines
+            .iter()
+            .position(|line| line == "\r\n")
+            .unwrap_or(lines.len());
+        Headers::parse(&lines[..separator])?;
+        Ok(Article::new_article(lines))
+    }
+}
+
+/// Standard Netnews headers (RFC 5536)
+///
+/// These accessors are lenient: values that do not follow the grammar are
+/// used as far as their meaning is clear, and missing or unusable headers give
+/// `None` or an empty list.
+///
+/// # Example
+///
+/// ```
+/// use nntp::Article;
+///
+/// let article = Article::new_article(vec![
+///     "Message-ID: <2@example.com>\r\n".to_string(),
+///     "References: <0@example.com>\r\n".to_string(),
+///     " <1@example.com>\r\n".to_string(),
+///     "Newsgroups: comp.lang.rust, misc.test\r\n".to_string(),
+///     "Xref: news.example.com comp.lang.rust:1234 misc.test:56\r\n".to_string(),
+///     "Date: Tue, 15 Nov 1994 08:12:31 -0500\r\n".to_string(),
+///     "Lines: 12\r\n".to_string(),
+/// ]);
+/// assert_eq!(article.message_id().unwrap().as_str(), "<2@example.com>");
+/// assert_eq!(article.references().len(), 2);
+/// assert_eq!(article.newsgroups(), vec!["comp.lang.rust", "misc.test"]);
+/// assert_eq!(
+///     article.xref(),
+///     vec![("comp.lang.rust".to_string(), 1234), ("misc.test".to_string(), 56)]
+/// );
+/// assert_eq!(article.date().unwrap().timestamp(), 784905151);
+/// assert_eq!(article.lines(), Some(12));
+/// assert_eq!(article.bytes(), None);
+/// ```
+impl Article {
+    /// Returns the `Message-ID` header, see [`MessageId::parse_lenient`].
+    pub fn message_id(&self) -> Option<MessageId> {
+        self.headers
+            .get("Message-ID")
+            .and_then(MessageId::parse_lenient)
+    }
+
+    /// Returns the message IDs in the `References` header, oldest first.
+    ///
+    /// IDs are found by their angle brackets, so missing whitespace or commas
+    /// between them do not matter. IDs that cannot be used are skipped.
+    pub fn references(&self) -> Vec<MessageId> {
+        let mut references = Vec::new();
+        let mut rest = self.headers.get("References").unwrap_or_default();
+        while let Some(start) = rest.find('<') {
+            let end = match rest[start..].find('>') {
+                Some(end) => start + end + 1,
+                None => break,
+            };
+            references.extend(MessageId::parse_lenient(&rest[start..end]));
+            rest = &rest[end..];
+        }
+        references
+    }
+
+    /// Returns the groups in the `Newsgroups` header.
+    pub fn newsgroups(&self) -> Vec<String> {
+        self.header_list("Newsgroups")
+    }
+
+    /// Returns the groups in the `Followup-To` header. A single `poster`
+    /// entry means followups should be sent to the author by email.
+    pub fn followup_to(&self) -> Vec<String> {
+        self.header_list("Followup-To")
+    }
+
+    /// Returns the `Distribution` header values.
+    pub fn distribution(&self) -> Vec<String> {
+        self.header_list("Distribution")
+    }
+
+    /// Returns the entries of the `Path` header, from the most recent site
+    /// to the one where the article was injected.
+    pub fn path(&self) -> Vec<String> {
+        self.headers
+            .get("Path")
+            .unwrap_or_default()
+            .split('!')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Returns the `Date` header, see [`DateTime::parse_rfc5322`].
+    pub fn date(&self) -> Option<DateTime> {
+        self.headers
+            .get("Date")
+            .and_then(|date| DateTime::parse_rfc5322(date).ok())
+    }
+
+    /// Returns the number of body lines from the `Lines` header.
+    pub fn lines(&self) -> Option<usize> {
+        self.headers
+            .get("Lines")
+            .and_then(|lines| lines.trim().parse().ok())
+    }
+
+    /// Returns the size of the article in octets from the `Bytes` header.
+    pub fn bytes(&self) -> Option<usize> {
+        self.headers
+            .get("Bytes")
+            .and_then(|bytes| bytes.trim().parse().ok())
+    }
+
+    /// Returns the group names and article numbers in the `Xref` header.
+    ///
+    /// The server name the header starts with is left out, as are entries
+    /// without a valid article number.
+    pub fn xref(&self) -> Vec<(String, u64)> {
+        self.headers
+            .get("Xref")
+            .unwrap_or_default()
+            .split_whitespace()
+            .filter_map(|entry| {
+                let (group, number) = entry.rsplit_once(':')?;
+                let number = number.parse().ok()?;
+                (!group.is_empty()).then(|| (group.to_string(), number))
+            })
+            .collect()
+    }
+
+    /// Splits a comma-separated header, allowing spaces instead of commas.
+    fn header_list(&self, name: &str) -> Vec<String> {
+        self.headers
+            .get(name)
+            .unwrap_or_default()
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|entry| !entry.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+impl TryFrom<Vec<String>> for Article {
+    type Error = NNTPError;
+
+    /// Parses raw article lines, see [`Article::parse`].
+    fn try_from(lines: Vec<String>) -> Result<Article> {
+        Article::parse(lines)
+    }
+}
+
+impl fmt::Display for Article {
+    /// Writes the article as it is sent to the server: the headers, a blank
+    /// line, then the body. Headers added with [`Headers::append`] are folded
+    /// to keep lines short; pass the result to
+    /// [`NNTPStream::post_article`](crate::NNTPStream::post_article) to post it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nntp::{Article, Headers};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.append("From", "user@example.com");
+    /// headers.append("Newsgroups", "comp.test");
+    /// headers.append("Subject", "Hello");
+    /// let article = Article {
+    ///     headers,
+    ///     body: vec!["Hello world!\r\n".to_string()],
+    /// };
+    /// assert_eq!(
+    ///     article.to_string(),
+    ///     "From: user@example.com\r\nNewsgroups: comp.test\r\nSubject: Hello\r\n\r\nHello world!\r\n"
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}\r\n", self.headers)?;
+        for line in &self.body {
+            f.write_str(line)?;
         }
-        Article { headers, body }
+        Ok(())
     }
 }
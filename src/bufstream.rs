@@ -0,0 +1,102 @@
+use std::io::{self, BufRead, Read, Write};
+
+/// Size of the read buffer, large enough for most responses to arrive in a
+/// handful of reads.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// A stream with a read buffer, so that responses are read from the socket
+/// in large chunks instead of byte by byte. Writes go straight through.
+///
+/// Unlike [`std::io::BufReader`], the inner stream stays writable, and it can
+/// be swapped out when the connection is upgraded (e.g. by `STARTTLS`).
+pub(crate) struct BufStream<S> {
+    inner: S,
+    buf: Box<[u8]>,
+    pos: usize,
+    filled: usize,
+}
+
+impl<S> BufStream<S> {
+    pub(crate) fn new(inner: S) -> Self {
+        BufStream {
+            inner,
+            buf: vec![0; BUFFER_SIZE].into_boxed_slice(),
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    pub(crate) fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns the inner stream, for upgrading it in place.
+    ///
+    /// The caller must check [`BufStream::has_buffered_data`] first: data
+    /// read before the upgrade would otherwise be mixed with data after it.
+    pub(crate) fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Drops any data read from the inner stream but not consumed.
+    pub(crate) fn discard_buffer(&mut self) {
+        self.pos = 0;
+        self.filled = 0;
+    }
+
+    /// Whether data has been read from the inner stream but not consumed.
+    pub(crate) fn has_buffered_data(&self) -> bool {
+        self.pos < self.filled
+    }
+}
+
+impl<S> std::fmt::Debug for BufStream<S>
+where
+    S: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BufStream")
+            .field("inner", &self.inner)
+            .field("buffered", &(self.filled - self.pos))
+            .finish()
+    }
+}
+
+impl<S: Read> Read for BufStream<S> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        // large reads skip the buffer when it is empty
+        if !self.has_buffered_data() && out.len() >= self.buf.len() {
+            return self.inner.read(out);
+        }
+
+        let available = self.fill_buf()?;
+        let n = available.len().min(out.len());
+        out[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<S: Read> BufRead for BufStream<S> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if !self.has_buffered_data() {
+            self.filled = self.inner.read(&mut self.buf)?;
+            self.pos = 0;
+        }
+        Ok(&self.buf[self.pos..self.filled])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.pos = (self.pos + amount).min(self.filled);
+    }
+}
+
+impl<S: Write> Write for BufStream<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,142 @@
+use std::string::String;
+use std::vec::Vec;
+
+/// The capabilities advertised by a server, as returned by `CAPABILITIES`.
+///
+/// See [RFC 3977 §5.2](https://tools.ietf.org/html/rfc3977#section-5.2). The
+/// capabilities may change after `MODE READER`, `STARTTLS` and successful
+/// authentication, so [`NNTPStream`](crate::NNTPStream) re-reads them when needed.
+///
+/// # Example
+///
+/// ```
+/// use nntp::Capabilities;
+///
+/// let lines = vec![
+///     "VERSION 2\r\n".to_string(),
+///     "READER\r\n".to_string(),
+///     "OVER MSGID\r\n".to_string(),
+///     "LIST ACTIVE NEWSGROUPS OVERVIEW.FMT\r\n".to_string(),
+///     "AUTHINFO USER SASL\r\n".to_string(),
+///     "SASL PLAIN SCRAM-SHA-256\r\n".to_string(),
+///     "IMPLEMENTATION INN 2.7.0\r\n".to_string(),
+/// ];
+/// let caps = Capabilities::from_capabilities_response(&lines);
+/// assert_eq!(caps.version, vec![2]);
+/// assert!(caps.reader);
+/// assert!(caps.over && caps.over_msgid);
+/// assert!(!caps.hdr);
+/// assert!(caps.supports_list("overview.fmt"));
+/// assert!(caps.supports_sasl("SCRAM-SHA-256"));
+/// assert_eq!(caps.implementation.as_deref(), Some("INN 2.7.0"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// The NNTP versions supported by the server (`VERSION`).
+    pub version: Vec<u32>,
+    /// Whether the server is in reader mode (`READER`).
+    pub reader: bool,
+    /// Whether `MODE READER` is needed to switch to reader mode (`MODE-READER`).
+    pub mode_reader: bool,
+    /// Whether posting is allowed (`POST`).
+    pub post: bool,
+    /// Whether articles can be transferred with `IHAVE` (`IHAVE`).
+    pub ihave: bool,
+    /// Whether streaming feeds are supported (`STREAMING`, RFC 4644).
+    pub streaming: bool,
+    /// Whether `NEWNEWS` is supported (`NEWNEWS`).
+    pub newnews: bool,
+    /// Whether `OVER` is supported (`OVER`).
+    pub over: bool,
+    /// Whether `OVER` accepts a message ID (`OVER MSGID`).
+    pub over_msgid: bool,
+    /// Whether `HDR` is supported (`HDR`).
+    pub hdr: bool,
+    /// The `LIST` keywords supported by the server, in upper case.
+    pub list: Vec<String>,
+    /// The `AUTHINFO` variants available now (e.g. `USER`, `SASL`), in upper case.
+    pub authinfo: Vec<String>,
+    /// The SASL mechanisms available now, in upper case (`SASL`, RFC 4643).
+    pub sasl: Vec<String>,
+    /// Whether `STARTTLS` is available (RFC 4642).
+    pub starttls: bool,
+    /// The compression algorithms available (`COMPRESS`, RFC 8054), in upper case.
+    pub compress: Vec<String>,
+    /// A description of the server software (`IMPLEMENTATION`).
+    pub implementation: Option<String>,
+    /// The raw capability lines, without their `\r\n` terminators.
+    pub lines: Vec<String>,
+}
+
+impl Capabilities {
+    /// Parses the lines returned by `CAPABILITIES`.
+    ///
+    /// Capability labels are case-insensitive; unknown ones are kept in
+    /// [`Capabilities::lines`] and can be checked with [`Capabilities::has`].
+    pub fn from_capabilities_response(lines: &[String]) -> Capabilities {
+        let chars_to_trim: &[char] = &['\r', '\n', ' ', '\t'];
+        let mut caps = Capabilities::default();
+
+        for line in lines.iter().map(|l| l.trim_matches(chars_to_trim)) {
+            if line.is_empty() {
+                continue;
+            }
+            caps.lines.push(line.to_string());
+
+            let mut words = line.split_whitespace();
+            let label = words.next().unwrap_or_default().to_ascii_uppercase();
+            let arguments = || words.clone().map(|w| w.to_ascii_uppercase()).collect();
+            match label.as_str() {
+                "VERSION" => caps.version = words.clone().filter_map(|v| v.parse().ok()).collect(),
+                "READER" => caps.reader = true,
+                "MODE-READER" => caps.mode_reader = true,
+                "POST" => caps.post = true,
+                "IHAVE" => caps.ihave = true,
+                "STREAMING" => caps.streaming = true,
+                "NEWNEWS" => caps.newnews = true,
+                "OVER" => {
+                    caps.over = true;
+                    caps.over_msgid = words.clone().any(|w| w.eq_ignore_ascii_case("MSGID"));
+                }
+                "HDR" => caps.hdr = true,
+                "LIST" => caps.list = arguments(),
+                "AUTHINFO" => caps.authinfo = arguments(),
+                "SASL" => caps.sasl = arguments(),
+                "STARTTLS" => caps.starttls = true,
+                "COMPRESS" => caps.compress = arguments(),
+                "IMPLEMENTATION" => {
+                    caps.implementation = Some(line[label.len()..].trim().to_string())
+                }
+                _ => (),
+            }
+        }
+        caps
+    }
+
+    /// Checks whether the server advertises the given capability label,
+    /// including ones this type has no field for.
+    pub fn has(&self, label: &str) -> bool {
+        self.lines.iter().any(|line| {
+            line.split_whitespace()
+                .next()
+                .is_some_and(|l| l.eq_ignore_ascii_case(label))
+        })
+    }
+
+    /// Checks whether the given `LIST` keyword is supported.
+    pub fn supports_list(&self, keyword: &str) -> bool {
+        self.list.iter().any(|k| k.eq_ignore_ascii_case(keyword))
+    }
+
+    /// Checks whether the given SASL mechanism is available.
+    pub fn supports_sasl(&self, mechanism: &str) -> bool {
+        self.sasl.iter().any(|m| m.eq_ignore_ascii_case(mechanism))
+    }
+
+    /// Checks whether the given compression algorithm is available.
+    pub fn supports_compress(&self, algorithm: &str) -> bool {
+        self.compress
+            .iter()
+            .any(|a| a.eq_ignore_ascii_case(algorithm))
+    }
+}
@@ -5,6 +5,10 @@
 //!
 //! - **RFC 3977**: Core NNTP protocol response codes (1xx–5xx)
 //! - **RFC 4643**: Authentication extension response codes (281, 283, 381, 383, 481, 482)
+//! - **RFC 4642**: TLS extension response codes (382, 580)
+//! - **RFC 4644**: Streaming extension response codes (203, 238, 239, 431, 438, 439)
+//! - **RFC 8054**: Compression extension response codes (206)
+//! - Common non-standard extensions (290 for `XFEATURE`)
 //!
 //! Response codes are grouped by their first digit:
 //! - `1xx` — Informational/follow-up responses (multi-line data follows)
@@ -332,6 +336,85 @@ pub enum ResponseCode {
     /// from RFC4643
     #[strum(to_string = "482 - Authentication Commands Out of Sequence")]
     AuthenticationOutOfSequence = 482,
+
+    // ─── RFC 4642: TLS extension ───
+    /// 382 - Continue with TLS negotiation.
+    /// Generated by: STARTTLS
+    /// Multi-line: no
+    /// from RFC4642
+    #[strum(to_string = "382 - Continue with TLS negotiation")]
+    ContinueWithTlsNegotiation = 382,
+
+    /// 580 - Can not initiate TLS negotiation.
+    /// Generated by: STARTTLS
+    /// Multi-line: no
+    /// from RFC4642
+    #[strum(to_string = "580 - Can not initiate TLS negotiation")]
+    TlsNegotiationFailed = 580,
+
+    // ─── RFC 4644: Streaming extension ───
+    /// 203 - Streaming permitted.
+    /// Generated by: MODE STREAM
+    /// Multi-line: no
+    /// from RFC4644
+    #[strum(to_string = "203 - Streaming permitted")]
+    StreamingPermitted = 203,
+
+    /// 238 - Send article to be transferred.
+    /// Generated by: CHECK
+    /// Arguments: 1 (message-id)
+    /// Multi-line: no
+    /// from RFC4644
+    #[strum(to_string = "238 - Send article to be transferred")]
+    CheckSendArticle = 238,
+
+    /// 239 - Article transferred OK.
+    /// Generated by: TAKETHIS
+    /// Arguments: 1 (message-id)
+    /// Multi-line: no
+    /// from RFC4644
+    #[strum(to_string = "239 - Article transferred OK")]
+    TakethisTransferred = 239,
+
+    /// 431 - Transfer not possible; try again later.
+    /// Generated by: CHECK
+    /// Arguments: 1 (message-id)
+    /// Multi-line: no
+    /// from RFC4644
+    #[strum(to_string = "431 - Transfer not possible; try again later")]
+    CheckTryLater = 431,
+
+    /// 438 - Article not wanted.
+    /// Generated by: CHECK
+    /// Arguments: 1 (message-id)
+    /// Multi-line: no
+    /// from RFC4644
+    #[strum(to_string = "438 - Article not wanted")]
+    CheckNotWanted = 438,
+
+    /// 439 - Transfer rejected; do not retry.
+    /// Generated by: TAKETHIS
+    /// Arguments: 1 (message-id)
+    /// Multi-line: no
+    /// from RFC4644
+    #[strum(to_string = "439 - Transfer rejected; do not retry")]
+    TakethisRejected = 439,
+
+    // ─── RFC 8054: Compression extension ───
+    /// 206 - Compression active.
+    /// Generated by: COMPRESS
+    /// Multi-line: no
+    /// from RFC8054
+    #[strum(to_string = "206 - Compression active")]
+    CompressionActive = 206,
+
+    // ─── Non-standard extensions ───
+    /// 290 - Feature enabled.
+    /// Generated by: XFEATURE
+    /// Multi-line: no
+    /// Not part of any RFC, used by several commercial providers
+    #[strum(to_string = "290 - Feature enabled")]
+    FeatureEnabled = 290,
 }
 
 impl From<ResponseCode> for u16 {
@@ -0,0 +1,361 @@
+use std::io::{self, BufRead, Read, Write};
+
+use flate2::read::DeflateDecoder;
+use flate2::{Compress, Compression, Decompress, FlushCompress, FlushDecompress, Status};
+
+/// Size of the buffer holding compressed data read from the inner stream.
+const BUFFER_SIZE: usize = 16 * 1024;
+
+/// A stream compressed in both directions with raw DEFLATE, as used by
+/// `COMPRESS DEFLATE` (RFC 8054).
+///
+/// Every write is flushed with a sync flush, so the server can decompress each
+/// command as soon as it arrives.
+pub(crate) struct DeflateStream<S> {
+    inner: S,
+    compress: Compress,
+    decompress: Decompress,
+    read_buf: Box<[u8]>,
+    read_pos: usize,
+    read_len: usize,
+    write_buf: Vec<u8>,
+}
+
+impl<S> DeflateStream<S> {
+    pub(crate) fn new(inner: S) -> Self {
+        DeflateStream {
+            inner,
+            // RFC 8054 uses raw DEFLATE, without zlib headers
+            compress: Compress::new(Compression::default(), false),
+            decompress: Decompress::new(false),
+            read_buf: vec![0; BUFFER_SIZE].into_boxed_slice(),
+            read_pos: 0,
+            read_len: 0,
+            write_buf: Vec::new(),
+        }
+    }
+
+    pub(crate) fn get_ref(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S> std::fmt::Debug for DeflateStream<S>
+where
+    S: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeflateStream")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Read> Read for DeflateStream<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            // inflate before reading more, as output that did not fit into
+            // `buf` last time may still be pending without any new input
+            let total_in = self.decompress.total_in();
+            let total_out = self.decompress.total_out();
+            let status = self
+                .decompress
+                .decompress(
+                    &self.read_buf[self.read_pos..self.read_len],
+                    buf,
+                    FlushDecompress::Sync,
+                )
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            let consumed = (self.decompress.total_in() - total_in) as usize;
+            let produced = (self.decompress.total_out() - total_out) as usize;
+            self.read_pos += consumed;
+
+            if produced > 0 {
+                return Ok(produced);
+            }
+            if status == Status::StreamEnd {
+                return Ok(0);
+            }
+            if self.read_pos == self.read_len {
+                let n = self.inner.read(&mut self.read_buf)?;
+                if n == 0 {
+                    return Ok(0);
+                }
+                self.read_pos = 0;
+                self.read_len = n;
+            } else if consumed == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "deflate stream made no progress",
+                ));
+            }
+        }
+    }
+}
+
+impl<S: Write> Write for DeflateStream<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let mut input = buf;
+        self.write_buf.clear();
+
+        loop {
+            self.write_buf.reserve(input.len() + 64);
+            let total_in = self.compress.total_in();
+            self.compress
+                .compress_vec(input, &mut self.write_buf, FlushCompress::Sync)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            input = &input[(self.compress.total_in() - total_in) as usize..];
+
+            // the flush is complete once the output no longer fills the buffer
+            if input.is_empty() && self.write_buf.len() < self.write_buf.capacity() {
+                break;
+            }
+        }
+
+        self.inner.write_all(&self.write_buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Reads a single zlib stream from `reader`, consuming nothing past its end,
+/// and returns the inflated data.
+///
+/// Used for `XFEATURE COMPRESS GZIP` responses, which are zlib streams
+/// despite the name.
+pub(crate) fn read_zlib_block(reader: &mut impl BufRead) -> io::Result<Vec<u8>> {
+    let mut decompress = Decompress::new(true);
+    let mut output = Vec::new();
+
+    loop {
+        let input = reader.fill_buf()?;
+        if input.is_empty() {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        output.reserve(input.len() * 4);
+        let total_in = decompress.total_in();
+        let status = decompress
+            .decompress_vec(input, &mut output, FlushDecompress::None)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        reader.consume((decompress.total_in() - total_in) as usize);
+        if status == Status::StreamEnd {
+            return Ok(output);
+        }
+    }
+}
+
+/// Inflates a complete raw DEFLATE stream, as sent by `XZVER`.
+pub(crate) fn inflate_raw(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut output = Vec::with_capacity(data.len() * 4);
+    DeflateDecoder::new(data).read_to_end(&mut output)?;
+    Ok(output)
+}
+
+/// Decodes the data lines of a single-part yEnc block, skipping the
+/// `=ybegin`, `=ypart` and `=yend` lines.
+pub(crate) fn yenc_decode(lines: &[Vec<u8>]) -> io::Result<Vec<u8>> {
+    let mut output = Vec::new();
+    let mut in_block = false;
+
+    for line in lines {
+        let line = line.strip_suffix(b"\r\n").unwrap_or(line);
+        if line.starts_with(b"=ybegin") {
+            in_block = true;
+            continue;
+        }
+        if in_block && line.starts_with(b"=yend") {
+            return Ok(output);
+        }
+        if !in_block || line.starts_with(b"=ypart") {
+            continue;
+        }
+
+        let mut bytes = line.iter();
+        while let Some(&byte) = bytes.next() {
+            let byte = if byte == b'=' {
+                match bytes.next() {
+                    Some(&escaped) => escaped.wrapping_sub(64),
+                    None => break,
+                }
+            } else {
+                byte
+            };
+            output.push(byte.wrapping_sub(42));
+        }
+    }
+
+    if in_block {
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "yEnc block is missing =yend",
+        ))
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "yEnc block is missing =ybegin",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufReader, Cursor};
+
+    /// Lines of pseudo-random hex, which compress poorly enough that the
+    /// compressed data spans many reads of the inflate buffer.
+    fn response_lines(count: usize) -> Vec<String> {
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        (0..count)
+            .map(|i| {
+                let words: Vec<String> = (0..8)
+                    .map(|_| {
+                        seed = seed
+                            .wrapping_mul(6364136223846793005)
+                            .wrapping_add(1442695040888963407);
+                        format!("{:016x}", seed)
+                    })
+                    .collect();
+                format!("{}\t{}\r\n", i, words.join(" "))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn deflate_stream_round_trip() {
+        let lines = response_lines(2000);
+        let mut server = DeflateStream::new(Vec::new());
+        server
+            .write_all(b"224 Overview information follows\r\n")
+            .unwrap();
+        for line in &lines {
+            server.write_all(line.as_bytes()).unwrap();
+        }
+        server.write_all(b".\r\n").unwrap();
+        let compressed = server.inner;
+        assert!(compressed.len() > 4 * BUFFER_SIZE);
+
+        let mut client = BufReader::new(DeflateStream::new(Cursor::new(compressed)));
+        let mut line = String::new();
+        client.read_line(&mut line).unwrap();
+        assert_eq!(line, "224 Overview information follows\r\n");
+        for expected in &lines {
+            line.clear();
+            client.read_line(&mut line).unwrap();
+            assert_eq!(&line, expected);
+        }
+        line.clear();
+        client.read_line(&mut line).unwrap();
+        assert_eq!(line, ".\r\n");
+    }
+
+    #[test]
+    fn deflate_stream_flushes_each_line() {
+        let mut client = DeflateStream::new(Vec::new());
+        client.write_all(b"GROUP misc.test\r\n").unwrap();
+        let first_command_len = client.inner.len();
+        client.write_all(b"OVER 1-10\r\n").unwrap();
+
+        // the first command can be inflated without anything written after it
+        let mut server =
+            DeflateStream::new(Cursor::new(client.inner[..first_command_len].to_vec()));
+        let mut command = [0u8; 64];
+        let n = server.read(&mut command).unwrap();
+        assert_eq!(&command[..n], b"GROUP misc.test\r\n");
+
+        let mut server = DeflateStream::new(Cursor::new(client.inner));
+        let mut commands = String::new();
+        server.read_to_string(&mut commands).unwrap();
+        assert_eq!(commands, "GROUP misc.test\r\nOVER 1-10\r\n");
+    }
+
+    // "hello" as a raw DEFLATE stream (fixed Huffman codes), and the same
+    // stream wrapped in a zlib header and Adler-32 trailer.
+    const HELLO_RAW: &[u8] = &[0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00];
+    const HELLO_ZLIB: &[u8] = &[
+        0x78, 0x9c, 0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00, 0x06, 0x2c, 0x02, 0x15,
+    ];
+
+    #[test]
+    fn inflate_raw_known_vector() {
+        assert_eq!(inflate_raw(HELLO_RAW).unwrap(), b"hello");
+        assert!(inflate_raw(&HELLO_RAW[..3]).is_err());
+    }
+
+    #[test]
+    fn read_zlib_block_stops_at_stream_end() {
+        let mut data = HELLO_ZLIB.to_vec();
+        data.extend_from_slice(b".\r\n");
+
+        // a tiny buffer makes the block span several fill_buf calls
+        let mut reader = BufReader::with_capacity(4, Cursor::new(data));
+        assert_eq!(read_zlib_block(&mut reader).unwrap(), b"hello");
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, ".\r\n");
+    }
+
+    #[test]
+    fn read_zlib_block_overview() {
+        let overview = response_lines(200).concat();
+        let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(overview.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut reader = Cursor::new(compressed.clone());
+        assert_eq!(read_zlib_block(&mut reader).unwrap(), overview.as_bytes());
+        assert_eq!(reader.position() as usize, compressed.len());
+
+        let mut truncated = Cursor::new(&compressed[..compressed.len() / 2]);
+        let error = read_zlib_block(&mut truncated).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    fn yenc_lines(lines: &[&[u8]]) -> Vec<Vec<u8>> {
+        lines.iter().map(|line| line.to_vec()).collect()
+    }
+
+    #[test]
+    fn yenc_decode_escapes() {
+        let lines = yenc_lines(&[
+            b"preamble text is ignored\r\n",
+            b"=ybegin part=1 line=128 size=8 name=test\r\n",
+            b"=ypart begin=1 end=8\r\n",
+            // NUL, LF, CR and '=' are escaped as '=' followed by the byte
+            // plus 64; '*' and '+' are the encodings of 0x00 and 0x01
+            b"=@=J=M=}\r\n",
+            b"*+\xff\x29\r\n",
+            b"=yend size=8 part=1\r\n",
+            b"trailing text is ignored\r\n",
+        ]);
+        assert_eq!(
+            yenc_decode(&lines).unwrap(),
+            [0xd6, 0xe0, 0xe3, 0x13, 0x00, 0x01, 0xd5, 0xff]
+        );
+    }
+
+    #[test]
+    fn yenc_decode_requires_markers() {
+        let error = yenc_decode(&yenc_lines(&[
+            b"=ybegin line=128 size=1 name=x\r\n",
+            b"*\r\n",
+        ]))
+        .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+        let error = yenc_decode(&yenc_lines(&[b"*\r\n", b"=yend size=1\r\n"])).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+}
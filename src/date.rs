@@ -0,0 +1,451 @@
+//! Dates as found in article headers.
+//!
+//! [`DateTime`] holds a calendar date and time with the UTC offset it was
+//! written in, and converts to and from Unix timestamps. It also reads and
+//! writes the `yyyymmddhhmmss` form used by the `DATE`, `NEWGROUPS` and
+//! `NEWNEWS` commands (RFC 3977 §7.1-7.4), which is always in UTC.
+
+use std::fmt;
+use std::str::FromStr;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::errors::{NNTPError, Result};
+
+const DAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// A date and time with a UTC offset, such as the value of a `Date` header.
+///
+/// # Example
+///
+/// ```
+/// use nntp::DateTime;
+///
+/// let date = DateTime::parse_rfc5322("Tue, 15 Nov 1994 08:12:31 -0500").unwrap();
+/// assert_eq!((date.year, date.month, date.day), (1994, 11, 15));
+/// assert_eq!(date.offset, -300);
+/// assert_eq!(date.timestamp(), 784905151);
+/// assert_eq!(date.to_string(), "Tue, 15 Nov 1994 08:12:31 -0500");
+/// assert_eq!(DateTime::from_timestamp(784905151).to_string(), "Tue, 15 Nov 1994 13:12:31 +0000");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    /// The year, e.g. `1994`.
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u8,
+    /// The day of the month, from 1.
+    pub day: u8,
+    /// The hour, from 0 to 23.
+    pub hour: u8,
+    /// The minute, from 0 to 59.
+    pub minute: u8,
+    /// The second, from 0 to 60 (for leap seconds).
+    pub second: u8,
+    /// The offset from UTC in minutes, positive east of Greenwich.
+    pub offset: i32,
+}
+
+impl DateTime {
+    /// Returns the UTC date and time of a Unix timestamp.
+    pub fn from_timestamp(timestamp: i64) -> DateTime {
+        let days = timestamp.div_euclid(86400);
+        let seconds = timestamp.rem_euclid(86400);
+        let (year, month, day) = civil_from_days(days);
+        DateTime {
+            year,
+            month,
+            day,
+            hour: (seconds / 3600) as u8,
+            minute: (seconds / 60 % 60) as u8,
+            second: (seconds % 60) as u8,
+            offset: 0,
+        }
+    }
+
+    /// Returns the number of seconds since the Unix epoch.
+    pub fn timestamp(&self) -> i64 {
+        days_from_civil(self.year, self.month, self.day) * 86400
+            + i64::from(self.hour) * 3600
+            + i64::from(self.minute) * 60
+            + i64::from(self.second)
+            - i64::from(self.offset) * 60
+    }
+
+    /// Parses a date in the RFC 5322 §3.3 format used by the `Date` header,
+    /// e.g. `Tue, 15 Nov 1994 08:12:31 -0500`.
+    ///
+    /// The obsolete syntax of RFC 5322 §4.3 is accepted too: zone names such
+    /// as `GMT` or `EST`, and two- or three-digit years (`94` is 1994, `03`
+    /// is 2003, `103` is 2003). Real-world dates are accepted where the
+    /// meaning is clear: the day of the week and the seconds may be missing,
+    /// comments are ignored, names are matched case-insensitively, and a
+    /// missing or unknown zone is read as UTC.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nntp::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc5322("Thu, 13 Feb 03 23:31 EST").unwrap();
+    /// assert_eq!(date.to_string(), "Thu, 13 Feb 2003 23:31:00 -0500");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] if the date, month, year or time is
+    /// missing or out of range.
+    pub fn parse_rfc5322(date: &str) -> Result<DateTime> {
+        let invalid = |reason: &str| NNTPError::ParseError {
+            line: date.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let cleaned = strip_comments(date).replace(',', " ");
+        let mut tokens: Vec<&str> = cleaned.split_whitespace().collect();
+        if tokens
+            .first()
+            .is_some_and(|t| t.chars().all(|c| c.is_ascii_alphabetic()))
+        {
+            // the day of the week is implied by the date
+            tokens.remove(0);
+        }
+        if tokens.len() < 4 {
+            return Err(invalid("expected `day month year time zone`"));
+        }
+
+        let day: u8 = tokens[0].parse().map_err(|_| invalid("invalid day"))?;
+        let month = parse_month(tokens[1]).ok_or_else(|| invalid("invalid month"))?;
+        let year = parse_year(tokens[2]).ok_or_else(|| invalid("invalid year"))?;
+        let (hour, minute, second) =
+            parse_time(tokens[3]).ok_or_else(|| invalid("invalid time"))?;
+        let offset = tokens.get(4).and_then(|zone| parse_zone(zone)).unwrap_or(0);
+
+        if day == 0 || day > days_in_month(year, month) {
+            return Err(invalid("day out of range"));
+        }
+        Ok(DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            offset,
+        })
+    }
+
+    /// Parses the `yyyymmddhhmmss` form of the `DATE` response, which is in
+    /// UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] if the date is not 14 digits or is
+    /// out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nntp::DateTime;
+    ///
+    /// let date = DateTime::parse_nntp("19990623135624").unwrap();
+    /// assert_eq!(date.to_string(), "Wed, 23 Jun 1999 13:56:24 +0000");
+    /// assert_eq!(date.to_nntp(), "19990623 135624");
+    /// ```
+    pub fn parse_nntp(date: &str) -> Result<DateTime> {
+        let invalid = |reason: &str| NNTPError::ParseError {
+            line: date.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let digits = date.trim();
+        if digits.len() != 14 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid("expected `yyyymmddhhmmss`"));
+        }
+        let number = |range: std::ops::Range<usize>| digits[range].parse::<u8>().unwrap_or(0);
+        let date_time = DateTime {
+            year: digits[..4].parse().map_err(|_| invalid("invalid year"))?,
+            month: number(4..6),
+            day: number(6..8),
+            hour: number(8..10),
+            minute: number(10..12),
+            second: number(12..14),
+            offset: 0,
+        };
+
+        if date_time.month == 0 || date_time.month > 12 {
+            return Err(invalid("month out of range"));
+        }
+        if date_time.day == 0 || date_time.day > days_in_month(date_time.year, date_time.month) {
+            return Err(invalid("day out of range"));
+        }
+        if date_time.hour > 23 || date_time.minute > 59 || date_time.second > 60 {
+            return Err(invalid("time out of range"));
+        }
+        Ok(date_time)
+    }
+
+    /// Formats the date as `yyyymmdd hhmmss` in UTC, the form taken by
+    /// `NEWGROUPS` and `NEWNEWS` together with the `GMT` keyword.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nntp::DateTime;
+    ///
+    /// let date = DateTime::parse_rfc5322("Mon, 1 Jan 2024 01:30:00 +0200").unwrap();
+    /// assert_eq!(date.to_nntp(), "20231231 233000");
+    /// ```
+    pub fn to_nntp(&self) -> String {
+        let utc = self.to_utc();
+        format!(
+            "{:04}{:02}{:02} {:02}{:02}{:02}",
+            utc.year,
+            utc.month,
+            utc.day,
+            utc.hour,
+            utc.minute,
+            // servers expect seconds up to 59
+            utc.second.min(59)
+        )
+    }
+
+    /// Returns the same moment in UTC.
+    pub fn to_utc(&self) -> DateTime {
+        if self.offset == 0 {
+            return *self;
+        }
+        // convert whole minutes, so that a leap second is kept as it is
+        let mut utc = DateTime::from_timestamp(self.timestamp() - i64::from(self.second));
+        utc.second = self.second;
+        utc
+    }
+
+    fn weekday(&self) -> usize {
+        // 1970-01-01 was a Thursday
+        (days_from_civil(self.year, self.month, self.day) + 4).rem_euclid(7) as usize
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = NNTPError;
+
+    /// Parses a `Date` header value, see [`DateTime::parse_rfc5322`].
+    fn from_str(date: &str) -> Result<DateTime> {
+        DateTime::parse_rfc5322(date)
+    }
+}
+
+impl fmt::Display for DateTime {
+    /// Formats the date as in a `Date` header, e.g.
+    /// `Tue, 15 Nov 1994 08:12:31 -0500`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.offset < 0 { '-' } else { '+' };
+        write!(
+            f,
+            "{}, {} {} {:04} {:02}:{:02}:{:02} {}{:02}{:02}",
+            DAY_NAMES[self.weekday()],
+            self.day,
+            MONTH_NAMES[usize::from(self.month.clamp(1, 12) - 1)],
+            self.year,
+            self.hour,
+            self.minute,
+            self.second,
+            sign,
+            self.offset.unsigned_abs() / 60,
+            self.offset.unsigned_abs() % 60
+        )
+    }
+}
+
+/// Removes parenthesized comments, such as the `(UTC)` that often follows
+/// the zone.
+fn strip_comments(date: &str) -> String {
+    let mut depth = 0usize;
+    let mut cleaned = String::with_capacity(date.len());
+    for c in date.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => cleaned.push(c),
+            _ => (),
+        }
+    }
+    cleaned
+}
+
+fn parse_month(month: &str) -> Option<u8> {
+    let month = month.get(..3)?;
+    MONTH_NAMES
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(month))
+        .map(|i| i as u8 + 1)
+}
+
+/// Parses a year, reading two-digit years as 1950-2049 and three-digit
+/// years as counting from 1900 (RFC 5322 §4.3).
+fn parse_year(year: &str) -> Option<i32> {
+    if year.is_empty() || !year.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let value: i32 = year.parse().ok()?;
+    match year.len() {
+        1 | 2 if value < 50 => Some(value + 2000),
+        1 | 2 => Some(value + 1900),
+        3 => Some(value + 1900),
+        _ => Some(value),
+    }
+}
+
+/// Parses `hh:mm` or `hh:mm:ss`.
+fn parse_time(time: &str) -> Option<(u8, u8, u8)> {
+    let mut parts = time.split(':');
+    let hour: u8 = parts.next()?.parse().ok()?;
+    let minute: u8 = parts.next()?.parse().ok()?;
+    let second: u8 = match parts.next() {
+        Some(second) => second.parse().ok()?,
+        None => 0,
+    };
+    if parts.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    Some((hour, minute, second))
+}
+
+/// Parses a `+hhmm` or `-hhmm` zone, or an obsolete zone name, into minutes
+/// east of UTC.
+fn parse_zone(zone: &str) -> Option<i32> {
+    let (sign, digits) = match zone.as_bytes().first()? {
+        b'+' => (1, &zone[1..]),
+        b'-' => (-1, &zone[1..]),
+        _ => return parse_obsolete_zone(zone),
+    };
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i32 = digits[..2].parse().ok()?;
+    let minutes: i32 = digits[2..].parse().ok()?;
+    if minutes > 59 {
+        return None;
+    }
+    Some(sign * (hours * 60 + minutes))
+}
+
+/// Parses the zone names of RFC 5322 §4.3. Military zones other than `Z`
+/// were defined with the wrong sign in RFC 822, so like unknown names they
+/// are read as UTC.
+fn parse_obsolete_zone(zone: &str) -> Option<i32> {
+    let hours = match zone.to_ascii_uppercase().as_str() {
+        "UT" | "UTC" | "GMT" | "Z" => 0,
+        "EDT" => -4,
+        "EST" | "CDT" => -5,
+        "CST" | "MDT" => -6,
+        "MST" | "PDT" => -7,
+        "PST" => -8,
+        _ => return None,
+    };
+    Some(hours * 60)
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Returns the number of days between 1970-01-01 and the given date, after
+/// Howard Hinnant's `days_from_civil`.
+fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The inverse of [`days_from_civil`].
+fn civil_from_days(days: i64) -> (i32, u8, u8) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u8;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u8;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year as i32, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(date: &str) -> DateTime {
+        DateTime::parse_rfc5322(date).unwrap()
+    }
+
+    #[test]
+    fn round_trips_across_offsets_and_leap_days() {
+        for text in [
+            "Thu, 29 Feb 2024 23:30:00 -0100",
+            "Tue, 29 Feb 2000 00:00:00 -1200",
+            "Wed, 28 Feb 1900 20:15:00 -0930",
+            "Fri, 1 Mar 2024 00:30:00 +0100",
+            "Thu, 1 Jan 1970 00:00:00 -0001",
+            "Wed, 31 Dec 1969 23:59:59 +0000",
+        ] {
+            let parsed = date(text);
+            assert_eq!(parsed.to_string(), text);
+            assert_eq!(date(&parsed.to_string()), parsed);
+            assert_eq!(
+                DateTime::from_timestamp(parsed.timestamp()),
+                parsed.to_utc()
+            );
+        }
+    }
+
+    #[test]
+    fn to_utc_crosses_leap_days() {
+        let utc = date("Thu, 29 Feb 2024 23:30:00 -0100").to_utc();
+        assert_eq!(utc.to_string(), "Fri, 1 Mar 2024 00:30:00 +0000");
+
+        let utc = date("Fri, 1 Mar 2024 00:30:00 +0100").to_utc();
+        assert_eq!(utc.to_string(), "Thu, 29 Feb 2024 23:30:00 +0000");
+
+        // 1900 is not a leap year
+        let utc = date("Thu, 1 Mar 1900 00:30:00 +0100").to_utc();
+        assert_eq!(utc.to_string(), "Wed, 28 Feb 1900 23:30:00 +0000");
+
+        let utc = date("Tue, 29 Feb 2000 20:15:00 -0930").to_utc();
+        assert_eq!(utc.to_string(), "Wed, 1 Mar 2000 05:45:00 +0000");
+    }
+
+    #[test]
+    fn to_utc_keeps_leap_seconds() {
+        let utc = date("Sat, 31 Dec 2016 18:59:60 -0500").to_utc();
+        assert_eq!(utc.to_string(), "Sat, 31 Dec 2016 23:59:60 +0000");
+        assert_eq!(utc.to_nntp(), "20161231 235959");
+
+        let utc = date("Sun, 1 Jan 2017 00:59:60 +0100").to_utc();
+        assert_eq!(utc.to_string(), "Sat, 31 Dec 2016 23:59:60 +0000");
+        assert_eq!(utc.to_utc(), utc);
+    }
+}
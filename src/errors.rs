@@ -46,6 +46,9 @@ pub enum NNTPError {
     #[error("Invalid Response forms server. Response: {response}")]
     InvalidResponse { response: String },
 
+    #[error("Failed parsing line from server: {reason}. line: {line:?}")]
+    ParseError { line: String, reason: String },
+
     #[error("Invalid message from server. likely reason: {reason} message: {message}")]
     InvalidMessage { message: String, reason: String },
 
@@ -57,6 +60,15 @@ pub enum NNTPError {
 
     #[error("TLS error: {message}")]
     TlsError { message: String },
+
+    #[error("SASL error: {message}")]
+    SaslError { message: String },
+
+    #[error("Compression error: {message}")]
+    CompressionError { message: String },
+
+    #[error("Not supported by the server: {message}")]
+    Unsupported { message: String },
 }
 
 /// Checks whether the given error represents a network-level error
@@ -0,0 +1,405 @@
+use std::fmt;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::errors::{NNTPError, Result};
+
+/// Length past which header lines are folded when serializing, as
+/// recommended by RFC 5322 §2.1.1.
+const FOLD_LENGTH: usize = 78;
+
+/// A single header field of an article.
+///
+/// Besides the name and value, the field keeps the lines it was parsed from,
+/// so that headers can be written back exactly as they were received. The
+/// value is unfolded: continuation lines are joined to the first one, without
+/// their line breaks (RFC 5322 §2.2.3).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderField {
+    name: String,
+    value: String,
+    raw: String,
+}
+
+impl HeaderField {
+    /// Creates a header field.
+    ///
+    /// The field is written as `name: value`, folded at whitespace so that
+    /// lines stay within 78 characters where possible. Any CR and LF
+    /// characters in the name or value are removed, so a value cannot end
+    /// the field early and inject further header lines.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nntp::headers::HeaderField;
+    ///
+    /// let references = "<1234@news.example.com> <5678@news.example.com> <9012@news.example.com>";
+    /// let field = HeaderField::new("References", references);
+    /// assert_eq!(
+    ///     field.raw(),
+    ///     "References: <1234@news.example.com> <5678@news.example.com>\r\n <9012@news.example.com>\r\n"
+    /// );
+    /// assert_eq!(field.value(), references);
+    ///
+    /// let field = HeaderField::new("Subject", "hello\r\nNewsgroups: alt.spam");
+    /// assert_eq!(field.raw(), "Subject: helloNewsgroups: alt.spam\r\n");
+    /// ```
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = strip_line_breaks(name.into());
+        let value = strip_line_breaks(value.into());
+        let raw = fold(&name, &value);
+        HeaderField { name, value, raw }
+    }
+
+    /// Parses the first line of a header field, splitting it on the first `:`
+    /// and dropping the single space that usually follows it.
+    fn parse(line: &str) -> Self {
+        let chars_to_trim: &[char] = &['\r', '\n'];
+        let trimmed_line = line.trim_matches(chars_to_trim);
+        let (name, value) = trimmed_line.split_once(':').unwrap_or((trimmed_line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        HeaderField {
+            name: name.to_string(),
+            value: value.to_string(),
+            raw: line.to_string(),
+        }
+    }
+
+    /// Joins a continuation line to the field.
+    fn unfold(&mut self, line: &str) {
+        let chars_to_trim: &[char] = &['\r', '\n'];
+        self.value.push_str(line.trim_end_matches(chars_to_trim));
+        self.raw.push_str(line);
+    }
+
+    /// The field name, as written by the sender.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The unfolded text after the colon and its following space.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// The field as it was received, or as it is sent for fields created
+    /// with [`HeaderField::new`], including its `\r\n` line endings.
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+}
+
+/// The headers of an article, in the order they were received.
+///
+/// Lookups are case-insensitive, as header names are (RFC 5322 §1.2.2), and
+/// repeated fields such as `Received` or `Path` are all kept. Formatting the
+/// headers with [`Display`](fmt::Display) writes the fields back as received.
+///
+/// # Example
+///
+/// ```
+/// use nntp::Headers;
+///
+/// let lines = vec![
+///     "Path: news.example.com!not-for-mail\r\n".to_string(),
+///     "Message-ID: <1@example.com>\r\n".to_string(),
+///     "Subject: a subject that\r\n".to_string(),
+///     "\tgoes on\r\n".to_string(),
+///     "X-Trace: first\r\n".to_string(),
+///     "X-Trace: second\r\n".to_string(),
+/// ];
+/// let headers = Headers::from_lines(&lines);
+/// assert_eq!(headers.get("message-id"), Some("<1@example.com>"));
+/// assert_eq!(headers.get("Subject"), Some("a subject that\tgoes on"));
+/// assert_eq!(headers.get_all("X-TRACE").collect::<Vec<_>>(), vec!["first", "second"]);
+/// assert_eq!(headers.fields()[0].raw(), "Path: news.example.com!not-for-mail\r\n");
+/// assert_eq!(headers.to_string(), lines.concat());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Headers {
+    fields: Vec<HeaderField>,
+}
+
+impl Headers {
+    /// Creates an empty set of headers.
+    pub fn new() -> Self {
+        Headers::default()
+    }
+
+    /// Parses header lines as returned by `HEAD`.
+    ///
+    /// Lines beginning with whitespace continue the field before them and are
+    /// unfolded into it. Parsing never fails: a line without a colon becomes a
+    /// field with an empty value. Use [`Headers::parse`] to reject such lines.
+    pub fn from_lines(lines: &[String]) -> Self {
+        let mut fields: Vec<HeaderField> = Vec::new();
+        for line in lines {
+            match fields.last_mut() {
+                Some(field) if line.starts_with([' ', '\t']) => field.unfold(line),
+                _ => fields.push(HeaderField::parse(line)),
+            }
+        }
+        Headers { fields }
+    }
+
+    /// Parses header lines like [`Headers::from_lines`], but checks that every
+    /// line is a well-formed field or continuation line.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] for the first line that has no colon,
+    /// has an empty or invalid field name, or continues a field when there is
+    /// none before it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nntp::{Headers, NNTPError};
+    ///
+    /// let lines = vec!["Subject: folded\r\n".to_string(), " value\r\n".to_string()];
+    /// assert_eq!(Headers::parse(&lines).unwrap().get("Subject"), Some("folded value"));
+    ///
+    /// let lines = vec!["Subject: hi\r\n".to_string(), "no colon here\r\n".to_string()];
+    /// match Headers::parse(&lines) {
+    ///     Err(NNTPError::ParseError { line, .. }) => assert_eq!(line, "no colon here\r\n"),
+    ///     other => panic!("unexpected {:?}", other),
+    /// }
+    /// ```
+    pub fn parse(lines: &[String]) -> Result<Self> {
+        for (i, line) in lines.iter().enumerate() {
+            if line.starts_with([' ', '\t']) {
+                if i == 0 {
+                    return Err(header_error(
+                        line,
+                        "continuation line before the first header",
+                    ));
+                }
+                continue;
+            }
+            let name = match line.split_once(':') {
+                Some((name, _)) => name,
+                None => return Err(header_error(line, "header line has no colon")),
+            };
+            // RFC 5322 §2.2: printable US-ASCII characters, except colon
+            if name.is_empty() || !name.bytes().all(|b| (33..=126).contains(&b)) {
+                return Err(header_error(line, "invalid header name"));
+            }
+        }
+        Ok(Headers::from_lines(lines))
+    }
+
+    /// Returns the value of the first field with the given name.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.get_all(name).next()
+    }
+
+    /// Returns the values of all fields with the given name, in order.
+    pub fn get_all<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a str> {
+        self.fields
+            .iter()
+            .filter(move |field| field.name.eq_ignore_ascii_case(name))
+            .map(|field| field.value.as_str())
+    }
+
+    /// Checks whether a field with the given name is present.
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Adds a field after the existing ones, see [`HeaderField::new`].
+    ///
+    /// CR and LF characters in `name` and `value` are removed.
+    pub fn append(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.fields.push(HeaderField::new(name, value));
+    }
+
+    /// Removes all fields with the given name, returning how many there were.
+    pub fn remove(&mut self, name: &str) -> usize {
+        let before = self.fields.len();
+        self.fields
+            .retain(|field| !field.name.eq_ignore_ascii_case(name));
+        before - self.fields.len()
+    }
+
+    /// Returns the fields in order, with access to their raw lines.
+    pub fn fields(&self) -> &[HeaderField] {
+        &self.fields
+    }
+
+    /// Returns the `(name, value)` pairs in order.
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            fields: self.fields.iter(),
+        }
+    }
+
+    /// Returns the number of fields.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Checks whether there are no fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+/// An iterator over the `(name, value)` pairs of [`Headers`].
+#[derive(Debug, Clone)]
+pub struct Iter<'a> {
+    fields: std::slice::Iter<'a, HeaderField>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.fields
+            .next()
+            .map(|field| (field.name.as_str(), field.value.as_str()))
+    }
+}
+
+impl<'a> IntoIterator for &'a Headers {
+    type Item = (&'a str, &'a str);
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Display for Headers {
+    /// Writes the fields as received, each ending in `\r\n`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for field in &self.fields {
+            f.write_str(&field.raw)?;
+        }
+        Ok(())
+    }
+}
+
+fn header_error(line: &str, reason: &str) -> NNTPError {
+    NNTPError::ParseError {
+        line: line.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Removes CR and LF characters, which would otherwise end the field.
+fn strip_line_breaks(mut text: String) -> String {
+    text.retain(|c| c != '\r' && c != '\n');
+    text
+}
+
+/// Writes a field as `name: value`, inserting a line break before whitespace
+/// wherever a line would otherwise grow past [`FOLD_LENGTH`]. Words longer
+/// than a line are left whole.
+fn fold(name: &str, value: &str) -> String {
+    let mut raw = String::with_capacity(name.len() + value.len() + 8);
+    raw.push_str(name);
+    raw.push(':');
+    if value.is_empty() {
+        raw.push_str("\r\n");
+        return raw;
+    }
+
+    let value = format!(" {}", value);
+    let mut line_length = raw.len();
+    let mut line_has_word = false;
+    let mut start = 0;
+    let is_space = |c: char| c == ' ' || c == '\t';
+    while start < value.len() {
+        // a segment is a run of whitespace followed by a word
+        let word = value[start..]
+            .find(|c: char| !is_space(c))
+            .map_or(value.len(), |i| start + i);
+        let end = value[word..]
+            .find(is_space)
+            .map_or(value.len(), |i| word + i);
+        let segment = &value[start..end];
+
+        // trailing whitespace stays put, a line of only whitespace is not allowed
+        if line_has_word && word < end && line_length + segment.len() > FOLD_LENGTH {
+            raw.push_str("\r\n");
+            line_length = 0;
+        }
+        raw.push_str(segment);
+        line_length += segment.len();
+        line_has_word = true;
+        start = end;
+    }
+    raw.push_str("\r\n");
+    raw
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_rejects_invalid_names() {
+        for line in ["Sub ject: hi\r\n", "Subject : hi\r\n", ": hi\r\n"] {
+            match Headers::parse(&lines(&["From: a@example.com\r\n", line])) {
+                Err(NNTPError::ParseError { line: bad, .. }) => assert_eq!(bad, line),
+                other => panic!("{:?} was accepted: {:?}", line, other),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_rejects_leading_continuation() {
+        assert!(Headers::parse(&lines(&[" folded\r\n", "Subject: hi\r\n"])).is_err());
+    }
+
+    // Space-separated words, `total` characters long
+    fn words(total: usize) -> String {
+        let mut value = String::new();
+        while value.len() < total {
+            value.push_str(if value.is_empty() { "word" } else { " word" });
+        }
+        value.truncate(total);
+        value
+    }
+
+    #[test]
+    fn fold_keeps_a_line_of_exactly_78_columns() {
+        let value = words(78 - "Subject: ".len());
+        let field = HeaderField::new("Subject", value.as_str());
+        assert_eq!(field.raw(), format!("Subject: {}\r\n", value));
+        assert_eq!(field.raw().len(), FOLD_LENGTH + 2);
+    }
+
+    #[test]
+    fn fold_breaks_past_78_columns() {
+        let value = format!("{} x", words(78 - "Subject: ".len()));
+        let field = HeaderField::new("Subject", value.as_str());
+        let raw_lines: Vec<&str> = field.raw().split_inclusive("\r\n").collect();
+        assert_eq!(raw_lines.len(), 2);
+        assert_eq!(raw_lines[0].len(), FOLD_LENGTH + 2);
+        assert_eq!(raw_lines[1], " x\r\n");
+
+        let headers = Headers::parse(&lines(&raw_lines)).unwrap();
+        assert_eq!(headers.get("Subject"), Some(value.as_str()));
+    }
+
+    #[test]
+    fn fold_leaves_long_words_whole() {
+        let long = "x".repeat(2 * FOLD_LENGTH);
+        let value = format!("{} a {} b", long, long);
+        let field = HeaderField::new("References", value.as_str());
+        assert_eq!(
+            field.raw(),
+            format!("References: {}\r\n a\r\n {}\r\n b\r\n", long, long)
+        );
+
+        let raw_lines: Vec<&str> = field.raw().split_inclusive("\r\n").collect();
+        let headers = Headers::parse(&lines(&raw_lines)).unwrap();
+        assert_eq!(headers.get("References"), Some(value.as_str()));
+    }
+}
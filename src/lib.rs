@@ -7,12 +7,35 @@
 //! ## Features
 //!
 //! - Connect to NNTP servers with automatic retry and exponential backoff
+//! - Typed `CAPABILITIES`, used to pick between standard and legacy commands
 //! - TLS encryption (auto-enabled on port 563 or `nntps://` scheme)
+//! - STARTTLS (RFC 4642) upgrade of plain connections
 //! - Retrieve articles by number or message ID
-//! - Fetch article headers, body, or full content
-//! - List and select newsgroups
-//! - Post messages to newsgroups
-//! - USER/PASS authentication with automatic re-authentication on reconnect
+//! - Fetch article headers, body, or full content, decoded or as raw bytes
+//! - Ordered, case-insensitive, multi-valued article headers
+//! - Typed accessors for standard Netnews headers (`Message-ID`, `References`,
+//!   `Newsgroups`, `Date`, `Xref`, ...)
+//! - Pipelined batch retrieval (`article_many`, `head_many`, `body_many`, `stat_many`)
+//! - Streaming iterators over large responses (`body_stream`, `list_stream`, ...)
+//! - Fetch overview data for article ranges (`OVER`, with `XOVER` fallback)
+//! - Fetch a single header across article ranges (`HDR`, with `XHDR` fallback)
+//! - List and select newsgroups, and list the articles they contain
+//! - `LIST` keywords (`ACTIVE`, `NEWSGROUPS`, `ACTIVE.TIMES`, `HEADERS`,
+//!   `DISTRIB.PATS`) with wildmat filtering
+//! - RFC 6048 `LIST` extensions (`COUNTS`, `DISTRIBUTIONS`, `MODERATORS`, `MOTD`,
+//!   `SUBSCRIPTIONS`)
+//! - Post messages to newsgroups, with automatic dot-stuffing and line ending
+//!   normalization
+//! - Transfer articles to peers with `IHAVE`, or pipelined with `CHECK` and
+//!   `TAKETHIS` (RFC 4644 streaming)
+//! - USER/PASS and SASL (`PLAIN`, `CRAM-MD5`, `SCRAM-SHA-256`, `EXTERNAL`)
+//!   authentication with automatic re-authentication on reconnect
+//! - TLS client certificates
+//! - `COMPRESS DEFLATE` (RFC 8054) connection compression
+//! - Compressed overview data with `XFEATURE COMPRESS GZIP` or `XZVER`
+//! - Dot-unstuffing of multi-line responses, with a raw mode that keeps them as sent
+//! - Date parsing for `Date` headers (including obsolete forms) and `DATE`, and
+//!   GMT dates for `NEWGROUPS` and `NEWNEWS`
 //! - UTF-8 and WINDOWS-1252 encoding support
 //!
 //! ## Quick Start
@@ -44,18 +67,38 @@
 
 pub mod address;
 pub mod article;
+mod bufstream;
+pub mod capabilities;
 pub mod codes;
+mod compress;
 mod connection;
+pub mod date;
 pub mod errors;
+pub mod headers;
+pub mod lines;
+pub mod message_id;
 pub mod newsgroup;
 pub mod nntp_stream;
+pub mod overview;
+pub mod range;
+pub mod sasl;
+pub mod streaming;
 pub mod tls;
 
 // re-export type for ease of use
 pub use address::ServerAddress;
 pub use article::Article;
+pub use capabilities::Capabilities;
 pub use codes::ResponseCode;
+pub use date::DateTime;
 pub use errors::{NNTPError, Result};
+pub use headers::Headers;
+pub use lines::ResponseLines;
+pub use message_id::MessageId;
 pub use newsgroup::NewsGroup;
-pub use nntp_stream::NNTPStream;
+pub use nntp_stream::{IhaveOutcome, ListHeadersVariant, NNTPStream, OverviewCompression};
+pub use overview::OverviewEntry;
+pub use range::ArticleRange;
+pub use sasl::SaslMechanism;
+pub use streaming::StreamingSession;
 pub use tls::TlsConfig;
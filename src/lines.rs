@@ -0,0 +1,157 @@
+//! Multi-line data blocks.
+//!
+//! Methods such as [`NNTPStream::body`] collect a whole response before
+//! returning it. For large articles or a full `LIST`, the `*_stream` methods
+//! return a [`ResponseLines`] iterator instead, which reads each line from
+//! the server as it is asked for.
+//!
+//! Lines of a data block that begin with a dot are sent with the dot doubled
+//! (RFC 3977 §3.1.1). [`unstuff_line`] undoes this; [`NNTPStream`] applies it
+//! to every multi-line response unless [raw mode](NNTPStream::set_raw_mode) is on.
+//! [`encode_data_block`] does the reverse for articles sent to the server.
+
+use std::string::String;
+
+use crate::errors::{NNTPError, Result};
+use crate::nntp_stream::NNTPStream;
+
+/// Removes the dot added in front of a data line that begins with a dot.
+///
+/// Lines that begin with a single dot are returned unchanged, so data from
+/// servers that do not dot-stuff is not corrupted.
+///
+/// # Example
+///
+/// ```
+/// use nntp::lines::unstuff_line;
+///
+/// assert_eq!(unstuff_line(b"..\r\n"), b".\r\n");
+/// assert_eq!(unstuff_line(b"..foo\r\n"), b".foo\r\n");
+/// assert_eq!(unstuff_line(b"...\r\n"), b"..\r\n");
+/// assert_eq!(unstuff_line(b"....yEnc\r\n"), b"...yEnc\r\n");
+/// assert_eq!(unstuff_line(b"foo..\r\n"), b"foo..\r\n");
+/// assert_eq!(unstuff_line(b".unstuffed\r\n"), b".unstuffed\r\n");
+/// ```
+pub fn unstuff_line(line: &[u8]) -> &[u8] {
+    if line.starts_with(b"..") {
+        &line[1..]
+    } else {
+        line
+    }
+}
+
+/// Encodes article content as a multi-line data block, ready to be sent.
+///
+/// Line endings (`\n`, `\r` or `\r\n`) are normalized to `\r\n`, lines that
+/// begin with a dot get a second one, and the terminating `.` line is
+/// appended. The content must not include the terminator itself.
+///
+/// # Example
+///
+/// ```
+/// use nntp::lines::encode_data_block;
+///
+/// let content = "Subject: dots\n\n.\n..two\nplain\n";
+/// assert_eq!(
+///     encode_data_block(content),
+///     "Subject: dots\r\n\r\n..\r\n...two\r\nplain\r\n.\r\n"
+/// );
+/// assert_eq!(encode_data_block("a\r\nb"), "a\r\nb\r\n.\r\n");
+/// assert_eq!(encode_data_block("a\rb\r\n"), "a\r\nb\r\n.\r\n");
+/// ```
+pub fn encode_data_block(content: &str) -> String {
+    let normalized = content.replace("\r\n", "\n").replace('\r', "\n");
+    let content = normalized.strip_suffix('\n').unwrap_or(&normalized);
+
+    let mut block = String::with_capacity(content.len() + content.len() / 32 + 5);
+    if !content.is_empty() {
+        for line in content.split('\n') {
+            if line.starts_with('.') {
+                block.push('.');
+            }
+            block.push_str(line);
+            block.push_str("\r\n");
+        }
+    }
+    block.push_str(".\r\n");
+    block
+}
+
+/// The lines of a multi-line response, read from the server one at a time.
+///
+/// Lines keep their `\r\n` terminator, as in the collected responses. The
+/// [`NNTPStream`] is borrowed until the terminating `.` line.
+///
+/// NNTP has no way to cancel a response, so dropping the iterator early
+/// reads the rest of the response and discards it before the connection can
+/// be used for the next command. Stopping partway through a large article
+/// saves memory but not bandwidth or time; to avoid the download, close the
+/// connection instead.
+///
+/// A line that cannot be decoded yields [`NNTPError::DecodingError`] and the
+/// iteration goes on with the next line. Connection errors end the iteration.
+///
+/// # Example
+///
+/// ```no_run
+/// use nntp::NNTPStream;
+///
+/// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+///     .expect("Failed to connect");
+/// let mut size = 0;
+/// for line in client.body_stream("<large.post@example.com>").expect("No such article") {
+///     size += line.expect("Failed to read body").len();
+/// }
+/// println!("{} bytes", size);
+/// ```
+#[derive(Debug)]
+pub struct ResponseLines<'a> {
+    stream: &'a mut NNTPStream,
+    done: bool,
+}
+
+impl<'a> ResponseLines<'a> {
+    /// Wraps a stream whose status line has just been read.
+    pub(crate) fn new(stream: &'a mut NNTPStream) -> Self {
+        ResponseLines {
+            stream,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for ResponseLines<'_> {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.stream.next_data_line() {
+            Ok(Some(line)) => Some(Ok(line)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            // the undecodable line was still consumed, the next one is intact
+            Err(NNTPError::DecodingError) => Some(Err(NNTPError::DecodingError)),
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl Drop for ResponseLines<'_> {
+    fn drop(&mut self) {
+        for line in self.by_ref() {
+            if let Err(e) = line
+                && !matches!(e, NNTPError::DecodingError)
+            {
+                log::warn!("Discarding the rest of a response failed: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Message IDs (RFC 5536 §3.1.3).
+
+use std::fmt;
+use std::str::FromStr;
+use std::string::String;
+
+use crate::errors::{NNTPError, Result};
+
+/// The longest message ID allowed, in octets.
+const MAX_LENGTH: usize = 250;
+
+/// The unique identifier of an article, including its angle brackets, e.g.
+/// `<45223423@example.com>`.
+///
+/// [`MessageId::parse`] only accepts IDs that follow the RFC 5536 grammar.
+/// Many IDs found on Usenet do not, so [`MessageId::parse_lenient`] also takes
+/// anything in angle brackets that could be sent in a command.
+///
+/// # Example
+///
+/// ```
+/// use nntp::MessageId;
+///
+/// let id = MessageId::parse(" <45223423@example.com>").unwrap();
+/// assert_eq!(id.as_str(), "<45223423@example.com>");
+/// assert!(id.is_valid());
+///
+/// assert!(MessageId::parse("<two..dots@example.com>").is_err());
+/// let id = MessageId::parse_lenient("<two..dots@example.com>").unwrap();
+/// assert!(!id.is_valid());
+///
+/// assert!(MessageId::parse_lenient("<has space@example.com>").is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MessageId {
+    id: String,
+}
+
+impl MessageId {
+    /// Parses a message ID, ignoring surrounding whitespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] if the ID does not follow the
+    /// RFC 5536 grammar.
+    pub fn parse(id: &str) -> Result<MessageId> {
+        let trimmed_id = id.trim();
+        if !is_valid_message_id(trimmed_id) {
+            return Err(NNTPError::ParseError {
+                line: id.to_string(),
+                reason: "invalid message ID".to_string(),
+            });
+        }
+        Ok(MessageId {
+            id: trimmed_id.to_string(),
+        })
+    }
+
+    /// Parses a message ID like [`MessageId::parse`], but also accepts IDs
+    /// that only break the grammar in ways servers tolerate: any printable
+    /// characters between the angle brackets, with or without an `@`.
+    ///
+    /// Returns `None` if there are no angle brackets, or if the ID contains
+    /// whitespace or control characters and so cannot be sent in a command.
+    pub fn parse_lenient(id: &str) -> Option<MessageId> {
+        let trimmed_id = id.trim();
+        let inner = trimmed_id.strip_prefix('<')?.strip_suffix('>')?;
+        if inner.is_empty()
+            || !inner
+                .bytes()
+                .all(|b| b > 32 && b != 127 && b != b'<' && b != b'>')
+        {
+            return None;
+        }
+        Some(MessageId {
+            id: trimmed_id.to_string(),
+        })
+    }
+
+    /// Returns the ID, including its angle brackets.
+    pub fn as_str(&self) -> &str {
+        &self.id
+    }
+
+    /// Checks whether the ID follows the RFC 5536 grammar. Only IDs from
+    /// [`MessageId::parse_lenient`] can fail this check.
+    pub fn is_valid(&self) -> bool {
+        is_valid_message_id(&self.id)
+    }
+}
+
+impl FromStr for MessageId {
+    type Err = NNTPError;
+
+    /// Parses a message ID, see [`MessageId::parse`].
+    fn from_str(id: &str) -> Result<MessageId> {
+        MessageId::parse(id)
+    }
+}
+
+impl fmt::Display for MessageId {
+    /// Writes the ID with its angle brackets, as used in commands.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.id)
+    }
+}
+
+impl AsRef<str> for MessageId {
+    fn as_ref(&self) -> &str {
+        &self.id
+    }
+}
+
+/// Checks `"<" id-left "@" id-right ">"`, where `id-left` is a dot-atom or a
+/// quoted string and `id-right` a dot-atom or a domain literal.
+fn is_valid_message_id(id: &str) -> bool {
+    if id.len() > MAX_LENGTH {
+        return false;
+    }
+    let core = match id.strip_prefix('<').and_then(|id| id.strip_suffix('>')) {
+        Some(core) => core,
+        None => return false,
+    };
+    let (left, right) = match core.rsplit_once('@') {
+        Some(parts) => parts,
+        None => return false,
+    };
+    (is_dot_atom_text(left) || is_no_fold_quote(left))
+        && (is_dot_atom_text(right) || is_no_fold_literal(right))
+}
+
+fn is_atext(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-/=?^_`{|}~".contains(&b)
+}
+
+fn is_dot_atom_text(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .split('.')
+            .all(|atom| !atom.is_empty() && atom.bytes().all(is_atext))
+}
+
+/// A quoted string without folding; `>` is excluded as in RFC 5536.
+fn is_no_fold_quote(text: &str) -> bool {
+    let inner = match text.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+        Some(inner) => inner.as_bytes(),
+        None => return false,
+    };
+    let mut i = 0;
+    while i < inner.len() {
+        match inner[i] {
+            b'\\' if i + 1 < inner.len() && (33..=126).contains(&inner[i + 1]) => i += 2,
+            b'"' | b'\\' | b'>' => return false,
+            b if (33..=126).contains(&b) => i += 1,
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// A domain literal: `[`, printable characters other than `[`, `]`, `\` and
+/// `>`, then `]`.
+fn is_no_fold_literal(text: &str) -> bool {
+    match text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+        Some(inner) => inner
+            .bytes()
+            .all(|b| (33..=126).contains(&b) && !b"[]\\>".contains(&b)),
+        None => false,
+    }
+}
@@ -2,6 +2,8 @@ use std::fmt;
 use std::str::FromStr;
 use std::string::String;
 
+use crate::errors::{NNTPError, Result};
+
 /// Information about a Usenet newsgroup.
 ///
 /// A newsgroup is a discussion forum on a specific topic. Each group has a
@@ -19,38 +21,70 @@ pub struct NewsGroup {
     /// The posting status of the group (e.g. `"y"` for yes, `"m"` for moderated).
     /// Empty when parsed from `GROUP` command responses.
     pub status: String,
+    /// The exact number of articles in the group, when known.
+    /// Only set when parsed from `LIST COUNTS` responses.
+    pub count: Option<isize>,
 }
 
 impl fmt::Display for NewsGroup {
-    /// Formats the newsgroup as `"name (article_count)"`.
+    /// Formats the newsgroup as `"name (article_count)"`, using the exact
+    /// count when known.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} ({})", self.name, self.high - self.low)
+        write!(
+            f,
+            "{} ({})",
+            self.name,
+            self.count.unwrap_or(self.high.saturating_sub(self.low))
+        )
     }
 }
 
 impl NewsGroup {
     /// Parses a newsgroup from a `LIST` command response line.
     ///
-    /// The expected format is: `group high low status`
+    /// The expected format is: `group high low status`. The same parser is
+    /// available through [`str::parse`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] if the line has fewer than four
+    /// fields or the `high` or `low` fields cannot be parsed as integers.
+    ///
+    /// # Example
     ///
-    /// # Panics
+    /// ```
+    /// use nntp::NewsGroup;
     ///
-    /// Panics if the `high` or `low` fields cannot be parsed as integers.
-    pub fn from_list_response(group: &str) -> NewsGroup {
+    /// let group = NewsGroup::from_list_response("misc.test 3002322 3000234 y\r\n").unwrap();
+    /// assert_eq!(group.name, "misc.test");
+    /// assert_eq!(group.number, 2088);
+    ///
+    /// let group: NewsGroup = "misc.test 3002322 3000234 m".parse().unwrap();
+    /// assert_eq!(group.status, "m");
+    ///
+    /// assert!(NewsGroup::from_list_response("misc.test 3002322\r\n").is_err());
+    /// ```
+    pub fn from_list_response(group: &str) -> Result<NewsGroup> {
         // group high low status
         let chars_to_trim: &[char] = &['\r', '\n', ' '];
-        let trimmed_group = group.trim_matches(chars_to_trim);
-        let split_group: Vec<&str> = trimmed_group.split(' ').collect();
+        let split_group: Vec<&str> = group
+            .trim_matches(chars_to_trim)
+            .split_whitespace()
+            .collect();
+        if split_group.len() < 4 {
+            return Err(parse_error(group, "expected `group high low status`"));
+        }
 
-        let high: isize = FromStr::from_str(split_group[1]).unwrap();
-        let low: isize = FromStr::from_str(split_group[2]).unwrap();
-        NewsGroup {
+        let high: isize = parse_number(group, "high", split_group[1])?;
+        let low: isize = parse_number(group, "low", split_group[2])?;
+        Ok(NewsGroup {
             name: split_group[0].to_string(),
             high,
             low,
-            number: high - low,
+            number: high.saturating_sub(low),
             status: split_group[3].to_string(),
-        }
+            count: None,
+        })
     }
 
     /// Parses a newsgroup from a `GROUP` command response.
@@ -58,21 +92,360 @@ impl NewsGroup {
     /// The expected format is: `211 number low high group`
     /// (the response code prefix is stripped before parsing).
     ///
-    /// # Panics
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] if the response has fewer than four
+    /// fields or the numeric fields cannot be parsed as integers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nntp::NewsGroup;
     ///
-    /// Panics if the numeric fields cannot be parsed as integers.
-    pub fn from_group_response(group: &str) -> NewsGroup {
+    /// let group = NewsGroup::from_group_response("1234 3000234 3002322 misc.test").unwrap();
+    /// assert_eq!(group.name, "misc.test");
+    /// assert_eq!(group.number, 1234);
+    ///
+    /// assert!(NewsGroup::from_group_response("1234 low 3002322 misc.test").is_err());
+    /// ```
+    pub fn from_group_response(group: &str) -> Result<NewsGroup> {
         // 211 number low high group
         let chars_to_trim: &[char] = &['\r', '\n', ' '];
-        let trimmed_group = group.trim_matches(chars_to_trim);
-        let split_group: Vec<&str> = trimmed_group.split(' ').collect();
-        NewsGroup {
-            number: FromStr::from_str(split_group[0]).unwrap(),
-            low: FromStr::from_str(split_group[1]).unwrap(),
-            high: FromStr::from_str(split_group[2]).unwrap(),
+        let split_group: Vec<&str> = group
+            .trim_matches(chars_to_trim)
+            .split_whitespace()
+            .collect();
+        if split_group.len() < 4 {
+            return Err(parse_error(group, "expected `number low high group`"));
+        }
+
+        Ok(NewsGroup {
+            number: parse_number(group, "number", split_group[0])?,
+            low: parse_number(group, "low", split_group[1])?,
+            high: parse_number(group, "high", split_group[2])?,
             name: split_group[3].to_string(),
             // status not returned in this command
             status: "".to_owned(),
+            count: None,
+        })
+    }
+
+    /// Parses a newsgroup from a `LIST COUNTS` response line (RFC 6048).
+    ///
+    /// The expected format is: `group high low count status`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] if the line has fewer than five
+    /// fields or a numeric field cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use nntp::NewsGroup;
+    ///
+    /// let group = NewsGroup::from_counts_response("misc.test 3002322 3000234 1234 y\r\n").unwrap();
+    /// assert_eq!(group.count, Some(1234));
+    /// assert_eq!(group.number, 1234);
+    /// assert_eq!(group.to_string(), "misc.test (1234)");
+    /// ```
+    pub fn from_counts_response(group: &str) -> Result<NewsGroup> {
+        // group high low count status
+        let chars_to_trim: &[char] = &['\r', '\n', ' '];
+        let split_group: Vec<&str> = group
+            .trim_matches(chars_to_trim)
+            .split_whitespace()
+            .collect();
+        if split_group.len() < 5 {
+            return Err(parse_error(group, "expected `group high low count status`"));
+        }
+
+        let high: isize = parse_number(group, "high", split_group[1])?;
+        let low: isize = parse_number(group, "low", split_group[2])?;
+        let count: isize = parse_number(group, "count", split_group[3])?;
+        Ok(NewsGroup {
+            name: split_group[0].to_string(),
+            high,
+            low,
+            number: count,
+            status: split_group[4].to_string(),
+            count: Some(count),
+        })
+    }
+}
+
+impl FromStr for NewsGroup {
+    type Err = NNTPError;
+
+    /// Parses a `LIST` response line, see [`NewsGroup::from_list_response`].
+    fn from_str(group: &str) -> Result<NewsGroup> {
+        NewsGroup::from_list_response(group)
+    }
+}
+
+/// A newsgroup name and its short description, as returned by `LIST NEWSGROUPS`.
+///
+/// # Example
+///
+/// ```
+/// use nntp::newsgroup::NewsGroupDescription;
+///
+/// let desc = NewsGroupDescription::from_list_response("misc.test\tFor testing\r\n").unwrap();
+/// assert_eq!(desc.name, "misc.test");
+/// assert_eq!(desc.description, "For testing");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewsGroupDescription {
+    /// The name of the newsgroup.
+    pub name: String,
+    /// The newsgroup's description, which may be empty.
+    pub description: String,
+}
+
+impl NewsGroupDescription {
+    /// Parses a `LIST NEWSGROUPS` response line.
+    ///
+    /// The expected format is: `group description`, separated by tabs or spaces.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] if the line is empty.
+    pub fn from_list_response(line: &str) -> Result<NewsGroupDescription> {
+        let chars_to_trim: &[char] = &['\r', '\n'];
+        let trimmed_line = line.trim_matches(chars_to_trim);
+        let (name, description) =
+            split_first_field(trimmed_line).ok_or_else(|| parse_error(line, "empty line"))?;
+        Ok(NewsGroupDescription {
+            name: name.to_string(),
+            description: description.trim().to_string(),
+        })
+    }
+}
+
+/// When and by whom a newsgroup was created, as returned by `LIST ACTIVE.TIMES`.
+///
+/// # Example
+///
+/// ```
+/// use nntp::newsgroup::GroupCreation;
+///
+/// let created = GroupCreation::from_list_response("misc.test 930445408 <creatme@isc.org>\r\n").unwrap();
+/// assert_eq!(created.name, "misc.test");
+/// assert_eq!(created.created, 930445408);
+/// assert_eq!(created.creator, "<creatme@isc.org>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupCreation {
+    /// The name of the newsgroup.
+    pub name: String,
+    /// The creation time, in seconds since the Unix epoch.
+    pub created: i64,
+    /// The entity that created the newsgroup, usually an email address.
+    pub creator: String,
+}
+
+impl GroupCreation {
+    /// Parses a `LIST ACTIVE.TIMES` response line.
+    ///
+    /// The expected format is: `group time creator`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] if the line has fewer than two
+    /// fields or the time is not a number.
+    pub fn from_list_response(line: &str) -> Result<GroupCreation> {
+        let chars_to_trim: &[char] = &['\r', '\n', ' '];
+        let mut fields = line.trim_matches(chars_to_trim).split_whitespace();
+        match (fields.next(), fields.next().map(|t| t.parse::<i64>())) {
+            (Some(name), Some(Ok(created))) => Ok(GroupCreation {
+                name: name.to_string(),
+                created,
+                creator: fields.next().unwrap_or_default().to_string(),
+            }),
+            _ => Err(parse_error(line, "expected `group time creator`")),
+        }
+    }
+}
+
+/// A rule for choosing the `Distribution` header of a new article, as returned
+/// by `LIST DISTRIB.PATS`.
+///
+/// # Example
+///
+/// ```
+/// use nntp::newsgroup::DistributionPattern;
+///
+/// let pattern = DistributionPattern::from_list_response("10:local.*:local\r\n").unwrap();
+/// assert_eq!(pattern.weight, 10);
+/// assert_eq!(pattern.wildmat, "local.*");
+/// assert_eq!(pattern.distribution, "local");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistributionPattern {
+    /// The weight of the rule; the highest matching weight wins.
+    pub weight: u32,
+    /// The wildmat the newsgroup names are matched against.
+    pub wildmat: String,
+    /// The value to use for the `Distribution` header.
+    pub distribution: String,
+}
+
+impl DistributionPattern {
+    /// Parses a `LIST DISTRIB.PATS` response line.
+    ///
+    /// The expected format is: `weight:wildmat:distribution`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] if the line does not have three
+    /// fields or the weight is not a number.
+    pub fn from_list_response(line: &str) -> Result<DistributionPattern> {
+        let chars_to_trim: &[char] = &['\r', '\n', ' '];
+        let mut fields = line.trim_matches(chars_to_trim).splitn(3, ':');
+        match (
+            fields.next().map(|w| w.parse::<u32>()),
+            fields.next(),
+            fields.next(),
+        ) {
+            (Some(Ok(weight)), Some(wildmat), Some(distribution)) => Ok(DistributionPattern {
+                weight,
+                wildmat: wildmat.to_string(),
+                distribution: distribution.to_string(),
+            }),
+            _ => Err(parse_error(line, "expected `weight:wildmat:distribution`")),
+        }
+    }
+}
+
+/// A distribution value and its description, as returned by
+/// `LIST DISTRIBUTIONS` (RFC 6048).
+///
+/// # Example
+///
+/// ```
+/// use nntp::newsgroup::Distribution;
+///
+/// let dist = Distribution::from_list_response("local\tLocal to this site\r\n").unwrap();
+/// assert_eq!(dist.name, "local");
+/// assert_eq!(dist.description, "Local to this site");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distribution {
+    /// The value used in the `Distribution` header.
+    pub name: String,
+    /// A short description of the distribution.
+    pub description: String,
+}
+
+impl Distribution {
+    /// Parses a `LIST DISTRIBUTIONS` response line.
+    ///
+    /// The expected format is: `distribution description`, separated by tabs
+    /// or spaces.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] if the line is empty.
+    pub fn from_list_response(line: &str) -> Result<Distribution> {
+        let chars_to_trim: &[char] = &['\r', '\n'];
+        let trimmed_line = line.trim_matches(chars_to_trim);
+        let (name, description) =
+            split_first_field(trimmed_line).ok_or_else(|| parse_error(line, "empty line"))?;
+        Ok(Distribution {
+            name: name.to_string(),
+            description: description.trim().to_string(),
+        })
+    }
+}
+
+/// The submission address template for moderated newsgroups, as returned by
+/// `LIST MODERATORS` (RFC 6048).
+///
+/// # Example
+///
+/// ```
+/// use nntp::newsgroup::ModeratorTemplate;
+///
+/// let template = ModeratorTemplate::from_list_response("*:%s@moderators.example.com\r\n").unwrap();
+/// assert_eq!(template.wildmat, "*");
+/// assert_eq!(
+///     template.submission_address("comp.lang.rust"),
+///     "comp-lang-rust@moderators.example.com"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModeratorTemplate {
+    /// The wildmat the moderated newsgroup names are matched against.
+    pub wildmat: String,
+    /// The submission address template. `%s` stands for the newsgroup name
+    /// with periods replaced by dashes, and `%%` for a literal `%`.
+    pub template: String,
+}
+
+impl ModeratorTemplate {
+    /// Parses a `LIST MODERATORS` response line.
+    ///
+    /// The expected format is: `wildmat:template`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] if the line has no `:` separator.
+    pub fn from_list_response(line: &str) -> Result<ModeratorTemplate> {
+        let chars_to_trim: &[char] = &['\r', '\n', ' '];
+        match line.trim_matches(chars_to_trim).split_once(':') {
+            Some((wildmat, template)) if !wildmat.is_empty() => Ok(ModeratorTemplate {
+                wildmat: wildmat.to_string(),
+                template: template.to_string(),
+            }),
+            _ => Err(parse_error(line, "expected `wildmat:template`")),
         }
     }
+
+    /// Expands the template into the submission address for `group`.
+    pub fn submission_address(&self, group: &str) -> String {
+        let mut address = String::with_capacity(self.template.len() + group.len());
+        let mut chars = self.template.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                address.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('s') => address.push_str(&group.replace('.', "-")),
+                Some('%') => address.push('%'),
+                Some(other) => {
+                    address.push('%');
+                    address.push(other);
+                }
+                None => address.push('%'),
+            }
+        }
+        address
+    }
+}
+
+/// Splits a line into its first whitespace-delimited field and the rest.
+fn split_first_field(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_start();
+    if line.is_empty() {
+        return None;
+    }
+    match line.find([' ', '\t']) {
+        Some(i) => Some((&line[..i], &line[i..])),
+        None => Some((line, "")),
+    }
+}
+
+fn parse_error(line: &str, reason: &str) -> NNTPError {
+    NNTPError::ParseError {
+        line: line.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+/// Parses the numeric `field` of a response line.
+fn parse_number(line: &str, field: &str, value: &str) -> Result<isize> {
+    value
+        .parse()
+        .map_err(|_| parse_error(line, &format!("`{}` is not a valid {}", value, field)))
 }
@@ -1,4 +1,5 @@
-use std::io::{Read, Write};
+use std::fmt::Display;
+use std::io::{BufRead, Read, Write};
 use std::net::TcpStream;
 use std::string::String;
 use std::vec::Vec;
@@ -7,17 +8,50 @@ use rustls::{ClientConnection, StreamOwned};
 
 use crate::address::ServerAddress;
 use crate::article::Article;
+use crate::bufstream::BufStream;
+use crate::capabilities::Capabilities;
 use crate::codes::{self, ResponseCode};
+use crate::compress::{self, DeflateStream};
 use crate::connection::connect_with_retry;
+use crate::date::DateTime;
 use crate::errors::{self, NNTPError, Result};
-use crate::newsgroup::NewsGroup;
+use crate::headers::Headers;
+use crate::lines::{self, ResponseLines};
+use crate::newsgroup::{
+    Distribution, DistributionPattern, GroupCreation, ModeratorTemplate, NewsGroup,
+    NewsGroupDescription,
+};
+use crate::overview::{OverviewEntry, OverviewFormat};
+use crate::range::ArticleRange;
+use crate::sasl::{self, ChannelBinding, SaslMechanism};
+use crate::streaming::StreamingSession;
 use crate::tls::wrap_tls;
 
-/// The underlying stream type — either plain TCP or TLS-wrapped.
+/// The underlying stream type — either plain TCP or TLS-wrapped, optionally
+/// compressed with `COMPRESS DEFLATE`.
 #[derive(Debug)]
 enum InnerStream {
     Plain(TcpStream),
     Tls(Box<StreamOwned<ClientConnection, TcpStream>>),
+    Deflate(Box<DeflateStream<InnerStream>>),
+    /// Left behind while the stream is being upgraded, or after an upgrade
+    /// failed and the connection can no longer be used.
+    Closed,
+}
+
+impl InnerStream {
+    fn closed_error() -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::NotConnected, "connection is closed")
+    }
+
+    /// Returns the TLS connection, looking through any compression layer.
+    fn tls_connection(&self) -> Option<&ClientConnection> {
+        match self {
+            InnerStream::Tls(s) => Some(&s.conn),
+            InnerStream::Deflate(s) => s.get_ref().tls_connection(),
+            _ => None,
+        }
+    }
 }
 
 impl Read for InnerStream {
@@ -25,6 +59,8 @@ impl Read for InnerStream {
         match self {
             InnerStream::Plain(s) => s.read(buf),
             InnerStream::Tls(s) => s.read(buf),
+            InnerStream::Deflate(s) => s.read(buf),
+            InnerStream::Closed => Err(InnerStream::closed_error()),
         }
     }
 }
@@ -34,6 +70,8 @@ impl Write for InnerStream {
         match self {
             InnerStream::Plain(s) => s.write(buf),
             InnerStream::Tls(s) => s.write(buf),
+            InnerStream::Deflate(s) => s.write(buf),
+            InnerStream::Closed => Err(InnerStream::closed_error()),
         }
     }
 
@@ -41,10 +79,23 @@ impl Write for InnerStream {
         match self {
             InnerStream::Plain(s) => s.flush(),
             InnerStream::Tls(s) => s.flush(),
+            InnerStream::Deflate(s) => s.flush(),
+            InnerStream::Closed => Err(InnerStream::closed_error()),
         }
     }
 }
 
+/// The number of pipelined commands that may await a response.
+const PIPELINE_WINDOW: usize = 16;
+
+/// Credentials of the last successful authentication, replayed by
+/// [`NNTPStream::re_connect`].
+#[derive(Debug)]
+enum Credentials {
+    UserPassword { username: String, password: String },
+    Sasl(Box<dyn SaslMechanism>),
+}
+
 /// A connection to an NNTP server.
 ///
 /// `NNTPStream` wraps a TCP connection (optionally TLS-encrypted) and provides
@@ -61,6 +112,10 @@ impl Write for InnerStream {
 /// For explicit TLS control, use [`NNTPStream::connect_with`] with a
 /// [`ServerAddress`] configured via [`ServerAddress::with_tls`].
 ///
+/// Servers that only offer plain connections can be secured with
+/// [`NNTPStream::starttls`], or automatically on connect with
+/// [`ServerAddress::with_starttls`].
+///
 /// # Example
 ///
 /// ```no_run
@@ -79,10 +134,27 @@ impl Write for InnerStream {
 #[derive(Debug)]
 pub struct NNTPStream {
     server_addr: ServerAddress,
-    stream: InnerStream,
+    stream: BufStream<InnerStream>,
+    /// Reused for every response line, to avoid an allocation per line.
+    line_buffer: Vec<u8>,
+    /// Leave multi-line responses dot-stuffed, see [`NNTPStream::set_raw_mode`].
+    raw_mode: bool,
     authenticated: bool,
-    username: Option<String>,
-    password: Option<String>,
+    credentials: Option<Credentials>,
+    /// Set once the server rejects `OVER`, so `XOVER` is used from then on.
+    use_xover: bool,
+    /// Set once the server rejects `HDR`, so `XHDR` is used from then on.
+    use_xhdr: bool,
+    /// The server's `LIST OVERVIEW.FMT`, fetched once per connection.
+    overview_fmt: Option<OverviewFormat>,
+    /// The group selected by the last successful `GROUP` or `LISTGROUP`.
+    current_group: Option<NewsGroup>,
+    /// The server's capabilities, discarded whenever they may have changed.
+    capabilities: Option<Capabilities>,
+    /// Set when the server does not support `CAPABILITIES` (RFC 977 servers).
+    capabilities_unsupported: bool,
+    /// The compressed overview extension enabled by the user, if any.
+    overview_compression: Option<OverviewCompression>,
 }
 
 /// Connection management
@@ -107,6 +179,9 @@ impl NNTPStream {
     /// server greeting is not recognized.
     /// Returns [`NNTPError::TlsError`] if the TLS handshake fails.
     ///
+    /// When the address was built with [`ServerAddress::with_starttls`], the
+    /// connection is upgraded with `STARTTLS` right after the greeting.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -138,7 +213,7 @@ impl NNTPStream {
     /// let addr = ServerAddress::with_tls(
     ///     "nntp.example.com",
     ///     563,
-    ///     TlsConfig { danger_accept_invalid_certs: true },
+    ///     TlsConfig { danger_accept_invalid_certs: true, ..Default::default() },
     /// );
     /// let client = NNTPStream::connect_with(addr);
     /// ```
@@ -151,25 +226,34 @@ impl NNTPStream {
         let addr_str = format!("{}:{}", server_addr.host, server_addr.port);
         let tcp_stream = connect_with_retry(&addr_str, 3, 7_0000, 100)?;
 
-        let stream = if server_addr.tls.is_some() {
-            let tls_stream =
-                wrap_tls(tcp_stream, &server_addr).map_err(|e| NNTPError::FailedConnecting {
+        let stream = if let Some(tls_config) = server_addr.implicit_tls() {
+            let tls_stream = wrap_tls(tcp_stream, &server_addr.host, tls_config).map_err(|e| {
+                NNTPError::FailedConnecting {
                     expected: "TLS handshake".to_owned(),
                     error: Box::new(NNTPError::TlsError {
                         message: e.to_string(),
                     }),
-                })?;
+                }
+            })?;
             InnerStream::Tls(Box::new(tls_stream))
         } else {
             InnerStream::Plain(tcp_stream)
         };
 
         let mut socket = NNTPStream {
-            stream,
+            stream: BufStream::new(stream),
+            line_buffer: Vec::new(),
+            raw_mode: false,
             server_addr,
             authenticated: false,
-            username: None,
-            password: None,
+            credentials: None,
+            use_xover: false,
+            use_xhdr: false,
+            overview_fmt: None,
+            current_group: None,
+            capabilities: None,
+            capabilities_unsupported: false,
+            overview_compression: None,
         };
 
         match socket.read_response(vec![
@@ -185,6 +269,17 @@ impl NNTPStream {
             }
         }
 
+        if socket.server_addr.requires_starttls() {
+            socket
+                .starttls()
+                .map_err(|err| NNTPError::FailedConnecting {
+                    expected: "STARTTLS".to_owned(),
+                    error: Box::new(err),
+                })?;
+        }
+
+        socket.auto_compress()?;
+
         Ok(socket)
     }
 
@@ -192,56 +287,183 @@ impl NNTPStream {
     ///
     /// This is useful after a connection has been lost. If the stream was
     /// previously authenticated, this method will automatically re-authenticate
-    /// using the stored credentials.
+    /// using the stored credentials, with whichever method succeeded last.
+    /// The credentials are kept when re-authentication fails, and so is the
+    /// [`NNTPStream::enable_overview_compression`] setting when the server
+    /// refuses it, so the next call tries again.
     ///
     /// # Errors
     ///
-    /// Returns [`NNTPError::FailedConnecting`] if reconnection fails, or
-    /// propagates authentication errors from [`NNTPStream::user_password_authenticate`].
+    /// Returns [`NNTPError::FailedConnecting`] if reconnection fails or the
+    /// server's greeting is not a 200 or 201 response, or propagates
+    /// authentication errors from [`NNTPStream::user_password_authenticate`]
+    /// or [`NNTPStream::authenticate_sasl`].
     pub fn re_connect(&mut self) -> Result<()> {
         let addr_str = format!("{}:{}", self.server_addr.host, self.server_addr.port);
         let tcp_stream = connect_with_retry(&addr_str, 3, 7_000, 100)?;
 
-        self.stream = if self.server_addr.tls.is_some() {
-            let tls_stream = wrap_tls(tcp_stream, &self.server_addr).map_err(|e| {
-                NNTPError::FailedConnecting {
-                    expected: "TLS handshake".to_owned(),
-                    error: Box::new(NNTPError::TlsError {
-                        message: e.to_string(),
-                    }),
-                }
-            })?;
+        let stream = if let Some(tls_config) = self.server_addr.implicit_tls() {
+            let tls_stream =
+                wrap_tls(tcp_stream, &self.server_addr.host, tls_config).map_err(|e| {
+                    NNTPError::FailedConnecting {
+                        expected: "TLS handshake".to_owned(),
+                        error: Box::new(NNTPError::TlsError {
+                            message: e.to_string(),
+                        }),
+                    }
+                })?;
             InnerStream::Tls(Box::new(tls_stream))
         } else {
             InnerStream::Plain(tcp_stream)
         };
+        self.stream = BufStream::new(stream);
+        self.overview_fmt = None;
+        self.current_group = None;
+        self.authenticated = false;
+        self.discard_capabilities();
 
-        let res = match self.read_response(vec![
+        match self.read_response(vec![
             ResponseCode::ServiceAvailablePostingAllowed,
             ResponseCode::ServiceAvailablePostingProhibited,
         ]) {
-            Ok((status, response)) => {
-                log::info!("Connect: {} {}", status, response);
-                Ok(())
+            Ok((status, response)) => log::info!("Connect: {} {}", status, response),
+            Err(err) => {
+                return Err(NNTPError::FailedConnecting {
+                    expected: "greeting response".to_owned(),
+                    error: Box::new(err),
+                });
             }
-            Err(err) => Err(NNTPError::FailedConnecting {
-                expected: "greeting response".to_owned(),
+        }
+
+        if self.server_addr.requires_starttls() {
+            self.starttls().map_err(|err| NNTPError::FailedConnecting {
+                expected: "STARTTLS".to_owned(),
                 error: Box::new(err),
-            }),
-        };
+            })?;
+        }
 
-        // if the server was authenticated, re-auth after reconnection
-        if self.authenticated {
-            self.authenticated = false;
-            if let (Some(username), Some(password)) = (self.username.clone(), self.password.clone())
-                && let Err(e) = self.user_password_authenticate(&username, &password)
-            {
-                log::warn!("Re-authentication after reconnect failed: {}", e);
-                return Err(e);
-            }
+        // if the server was authenticated, re-auth after reconnection; the
+        // credentials are kept even if this fails, so the next attempt retries
+        if self.credentials.is_some()
+            && let Err(e) = self.re_authenticate()
+        {
+            log::warn!("Re-authentication after reconnect failed: {}", e);
+            return Err(e);
+        }
+
+        self.auto_compress()?;
+
+        // XFEATURE only lasts for the connection it was sent on; the setting
+        // is kept if the server refuses it now, so the next attempt retries
+        if let Some(compression) = self.overview_compression.take()
+            && let Err(e) = self.enable_overview_compression(compression)
+        {
+            self.overview_compression = Some(compression);
+            return Err(e);
+        }
+
+        Ok(())
+    }
+
+    /// Upgrades the plain connection to TLS using `STARTTLS` (RFC 4642).
+    ///
+    /// The TLS handshake uses the address's [`TlsConfig`](crate::TlsConfig), or the default
+    /// configuration if none was given. Once the connection is secured, the
+    /// cached capabilities are discarded, since the server may advertise
+    /// different ones (e.g. `AUTHINFO USER` only over TLS). They are fetched
+    /// again when next needed.
+    ///
+    /// `STARTTLS` must be issued before authenticating.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::TlsError`] if the connection is already encrypted
+    /// or compressed, the server's capabilities do not list `STARTTLS`, or
+    /// the handshake fails. A failed handshake leaves the connection closed.
+    /// Returns a response error with code 580 if the server cannot start TLS,
+    /// or code 502 if the client is already authenticated.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::NNTPStream;
+    ///
+    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    /// client.starttls().expect("Failed to start TLS");
+    /// client.user_password_authenticate("user", "password")
+    ///     .expect("Authentication failed");
+    /// ```
+    pub fn starttls(&mut self) -> Result<()> {
+        if !matches!(self.stream.get_ref(), InnerStream::Plain(_)) {
+            return Err(NNTPError::TlsError {
+                message: "connection is already encrypted or compressed".to_owned(),
+            });
+        }
+        if self
+            .supported_capabilities()?
+            .is_some_and(|capabilities| !capabilities.starttls)
+        {
+            return Err(NNTPError::TlsError {
+                message: "server does not advertise STARTTLS".to_owned(),
+            });
+        }
+
+        self.send_command_expect_response(
+            "STARTTLS\r\n",
+            vec![ResponseCode::ContinueWithTlsNegotiation],
+        )?;
+
+        // anything received before the handshake could have been injected
+        // by an attacker, and must not be read as if it came over TLS
+        if self.stream.has_buffered_data() {
+            *self.stream.get_mut() = InnerStream::Closed;
+            return Err(NNTPError::TlsError {
+                message: "server sent data before the TLS handshake".to_owned(),
+            });
         }
 
-        res
+        let tcp_stream = match std::mem::replace(self.stream.get_mut(), InnerStream::Closed) {
+            InnerStream::Plain(tcp_stream) => tcp_stream,
+            _ => unreachable!("stream was checked to be plain"),
+        };
+        let tls_config = self.server_addr.tls.clone().unwrap_or_default();
+        let tls_stream =
+            wrap_tls(tcp_stream, &self.server_addr.host, &tls_config).map_err(|e| {
+                NNTPError::TlsError {
+                    message: e.to_string(),
+                }
+            })?;
+        *self.stream.get_mut() = InnerStream::Tls(Box::new(tls_stream));
+
+        self.discard_capabilities();
+        Ok(())
+    }
+
+    /// Turns raw mode on or off. Raw mode is off by default.
+    ///
+    /// Lines of multi-line responses that begin with a dot are sent with the
+    /// dot doubled (RFC 3977 §3.1.1), and the extra dot is normally removed.
+    /// In raw mode, the lines are returned exactly as sent, which archival
+    /// tools may prefer. The terminating `.` line is never returned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::NNTPStream;
+    ///
+    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    /// client.set_raw_mode(true);
+    /// let wire_lines = client.body_by_id("<archived@example.com>").expect("No such article");
+    /// ```
+    pub fn set_raw_mode(&mut self, raw: bool) {
+        self.raw_mode = raw;
+    }
+
+    /// Whether raw mode is on, see [`NNTPStream::set_raw_mode`].
+    pub fn raw_mode(&self) -> bool {
+        self.raw_mode
     }
 
     /// Authenticates with the server using the `AUTHINFO USER/PASS` method (RFC 4643).
@@ -258,6 +480,10 @@ impl NNTPStream {
     ///
     /// Returns [`NNTPError::ResponseCode`] if authentication fails (e.g. wrong
     /// credentials), or propagates I/O errors.
+    /// Returns [`NNTPError::TlsError`] if the address requires `STARTTLS` and
+    /// the connection has not been upgraded yet.
+    /// Returns [`NNTPError::CompressionError`] if compression is already
+    /// active, as RFC 8054 forbids authenticating after `COMPRESS`.
     ///
     /// # Example
     ///
@@ -272,24 +498,224 @@ impl NNTPStream {
     pub fn user_password_authenticate(&mut self, username: &str, password: &str) -> Result<()> {
         // TODO: allow posting mode too
 
+        self.check_starttls_done()?;
+        self.check_not_compressed()?;
+
         let user_response = self.auth_user(username)?;
 
         // If the server already accepted authentication with USER alone, skip PASS
         if user_response.starts_with("281") {
             self.authenticated = true;
-            self.username = Some(username.to_owned());
-            self.password = Some(password.to_owned());
-            return Ok(());
+            self.discard_capabilities();
+            self.credentials = Some(Credentials::UserPassword {
+                username: username.to_owned(),
+                password: password.to_owned(),
+            });
+            return self.auto_compress();
         }
 
         // Server responded with 381 (Password Required), send PASS
         self.auth_password(password)?;
 
         self.authenticated = true;
-        self.username = Some(username.to_owned());
-        self.password = Some(password.to_owned());
+        self.discard_capabilities();
+        self.credentials = Some(Credentials::UserPassword {
+            username: username.to_owned(),
+            password: password.to_owned(),
+        });
+        self.auto_compress()
+    }
+
+    /// Authenticates with the server using `AUTHINFO SASL` (RFC 4643).
+    ///
+    /// The exchange is driven by the given [`SaslMechanism`]; see the
+    /// [`sasl`](crate::sasl) module for the built-in `PLAIN`, `CRAM-MD5` and
+    /// `SCRAM-SHA-256` mechanisms. When the connection uses TLS, the
+    /// mechanism is offered `tls-exporter` channel binding data.
+    ///
+    /// The mechanism is kept so that [`NNTPStream::re_connect`] can
+    /// re-authenticate automatically.
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 481 if authentication fails, or
+    /// code 503 if the mechanism is not supported.
+    /// Returns [`NNTPError::SaslError`] if the mechanism rejects a server
+    /// challenge, in which case the exchange is cancelled.
+    /// Returns [`NNTPError::TlsError`] if the address requires `STARTTLS` and
+    /// the connection has not been upgraded yet.
+    /// Returns [`NNTPError::CompressionError`] if compression is already
+    /// active, as RFC 8054 forbids authenticating after `COMPRESS`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::NNTPStream;
+    /// use nntp::sasl::Plain;
+    ///
+    /// let mut client = NNTPStream::connect("nntps://nntp.example.com".to_string())
+    ///     .expect("Failed to connect");
+    /// client.authenticate_sasl(Plain::new("user", "password"))
+    ///     .expect("Authentication failed");
+    /// ```
+    pub fn authenticate_sasl(&mut self, mechanism: impl SaslMechanism + 'static) -> Result<()> {
+        self.authenticate_sasl_boxed(Box::new(mechanism))
+    }
+
+    /// Authenticates with the server using `AUTHINFO SASL EXTERNAL`, relying
+    /// on the TLS client certificate set in
+    /// [`TlsConfig::client_cert`](crate::TlsConfig::client_cert).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::TlsError`] if the connection is not encrypted or no
+    /// client certificate is configured.
+    /// Returns a response error with code 481 if the server does not accept
+    /// the certificate.
+    ///
+    /// # Example
+    ///
+    /// See [`ClientCertificate`](crate::tls::ClientCertificate).
+    pub fn authenticate_sasl_external(&mut self) -> Result<()> {
+        let has_client_cert = self
+            .server_addr
+            .tls
+            .as_ref()
+            .is_some_and(|tls| tls.client_cert.is_some());
+        if !has_client_cert || self.stream.get_ref().tls_connection().is_none() {
+            return Err(NNTPError::TlsError {
+                message: "EXTERNAL requires a TLS connection with a client certificate".to_owned(),
+            });
+        }
+
+        self.authenticate_sasl(sasl::External::new())
+    }
+
+    fn authenticate_sasl_boxed(&mut self, mut mechanism: Box<dyn SaslMechanism>) -> Result<()> {
+        self.sasl_login(mechanism.as_mut())?;
+        self.credentials = Some(Credentials::Sasl(mechanism));
+        self.auto_compress()
+    }
+
+    fn sasl_login(&mut self, mechanism: &mut dyn SaslMechanism) -> Result<()> {
+        self.check_starttls_done()?;
+        self.check_not_compressed()?;
+        self.sasl_exchange(mechanism)?;
+
+        self.authenticated = true;
+        self.discard_capabilities();
+        Ok(())
+    }
+
+    // Authenticates again with the stored credentials, putting them back
+    // whether or not this succeeds.
+    fn re_authenticate(&mut self) -> Result<()> {
+        match self.credentials.take() {
+            Some(Credentials::UserPassword { username, password }) => {
+                let result = self.user_password_authenticate(&username, &password);
+                if result.is_err() {
+                    self.credentials = Some(Credentials::UserPassword { username, password });
+                }
+                result
+            }
+            Some(Credentials::Sasl(mut mechanism)) => {
+                let result = self.sasl_login(mechanism.as_mut());
+                self.credentials = Some(Credentials::Sasl(mechanism));
+                result
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Enables `COMPRESS DEFLATE` (RFC 8054) for the rest of the connection.
+    ///
+    /// Both directions are compressed with raw DEFLATE, which greatly reduces
+    /// the size of overview and header listings. Compression can only be
+    /// enabled once per connection; [`NNTPStream::re_connect`] starts without
+    /// it, unless [`ServerAddress::compress`] is set.
+    ///
+    /// Authentication is not allowed once compression is active, so this is
+    /// usually called after [`NNTPStream::user_password_authenticate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::CompressionError`] if compression is already active.
+    /// Returns a response error with code 403 if the server cannot enable
+    /// compression, or code 502 if it is not available.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::NNTPStream;
+    ///
+    /// let mut client = NNTPStream::connect("nntps://nntp.example.com".to_string())
+    ///     .expect("Failed to connect");
+    /// client.user_password_authenticate("user", "password")
+    ///     .expect("Authentication failed");
+    /// if client.capabilities().is_ok_and(|caps| caps.supports_compress("DEFLATE")) {
+    ///     client.compress_deflate().expect("Failed to enable compression");
+    /// }
+    /// ```
+    pub fn compress_deflate(&mut self) -> Result<()> {
+        if self.is_compressed() {
+            return Err(NNTPError::CompressionError {
+                message: "compression is already active".to_owned(),
+            });
+        }
+
+        self.send_command_expect_response(
+            "COMPRESS DEFLATE\r\n",
+            vec![ResponseCode::CompressionActive],
+        )?;
+
+        if self.stream.has_buffered_data() {
+            *self.stream.get_mut() = InnerStream::Closed;
+            return Err(NNTPError::CompressionError {
+                message: "server sent data before compression started".to_owned(),
+            });
+        }
+        let stream = std::mem::replace(self.stream.get_mut(), InnerStream::Closed);
+        *self.stream.get_mut() = InnerStream::Deflate(Box::new(DeflateStream::new(stream)));
+
+        // the server no longer advertises COMPRESS
+        self.discard_capabilities();
         Ok(())
     }
+
+    /// Whether `COMPRESS DEFLATE` is active on this connection.
+    pub fn is_compressed(&self) -> bool {
+        matches!(self.stream.get_ref(), InnerStream::Deflate(_))
+    }
+
+    // Enables compression if the address asks for it and the server
+    // currently advertises it. Authentication is not allowed once compression
+    // is active (RFC 8054 §2.2.2), so while the server offers it, compression
+    // waits until the client has authenticated.
+    fn auto_compress(&mut self) -> Result<()> {
+        if !self.server_addr.compress || self.is_compressed() {
+            return Ok(());
+        }
+        let authenticated = self.authenticated;
+        let ready = self.supported_capabilities()?.is_some_and(|capabilities| {
+            capabilities.supports_compress("DEFLATE")
+                && (authenticated || capabilities.authinfo.is_empty())
+        });
+        if !ready {
+            return Ok(());
+        }
+
+        match self.compress_deflate() {
+            Ok(()) => Ok(()),
+            Err(NNTPError::ResponseCode { received, .. }) => {
+                log::warn!(
+                    "COMPRESS DEFLATE refused with {}, continuing uncompressed",
+                    received
+                );
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
 }
 
 /// Article retrieval commands (RFC 3977 §6)
@@ -374,7 +800,7 @@ impl NNTPStream {
     /// # Errors
     ///
     /// Returns [`NNTPError::ArticleUnavailable`] if no article exists at the current number.
-    pub fn head(&mut self) -> Result<Vec<String>> {
+    pub fn head(&mut self) -> Result<Headers> {
         self.retrieve_head("HEAD\r\n")
     }
 
@@ -383,7 +809,7 @@ impl NNTPStream {
     /// # Errors
     ///
     /// Returns [`NNTPError::ArticleUnavailable`] if the message ID is not found.
-    pub fn head_by_id(&mut self, article_id: &str) -> Result<Vec<String>> {
+    pub fn head_by_id(&mut self, article_id: &str) -> Result<Headers> {
         self.retrieve_head(&format!("HEAD {}\r\n", article_id))
     }
 
@@ -393,7 +819,7 @@ impl NNTPStream {
     /// # Errors
     ///
     /// Returns [`NNTPError::ArticleUnavailable`] if no article exists with that number.
-    pub fn head_by_number(&mut self, article_number: isize) -> Result<Vec<String>> {
+    pub fn head_by_number(&mut self, article_number: isize) -> Result<Headers> {
         self.retrieve_head(&format!("HEAD {}\r\n", article_number))
     }
 
@@ -425,14 +851,22 @@ impl NNTPStream {
     pub fn stat_by_number(&mut self, article_number: isize) -> Result<String> {
         self.retrieve_stat(&format!("STAT {}\r\n", article_number))
     }
-}
 
-/// Information and listing commands (RFC 3977 §7)
-impl NNTPStream {
-    /// Retrieves the list of capabilities supported by the server.
+    /// Retrieves several articles, given by message ID or by number in the
+    /// currently selected newsgroup, with pipelined `ARTICLE` commands.
+    ///
+    /// Up to 16 commands are sent ahead of their responses (RFC 3977 §3.5),
+    /// which saves a round trip per article. Results are returned in the
+    /// order of `articles`.
+    ///
+    /// # Errors
     ///
-    /// Returns a list of capability labels. Each entry may include optional
-    /// arguments in parentheses.
+    /// A missing article only fails its own entry, with
+    /// [`NNTPError::ArticleUnavailable`] or a response error with code 430.
+    /// The outer error is returned if the connection fails or a response
+    /// cannot be read, in which case the whole batch is abandoned. Responses
+    /// to the commands already sent would then be read by later commands, so
+    /// the connection is closed; use [`NNTPStream::re_connect`] to go on.
     ///
     /// # Example
     ///
@@ -441,48 +875,49 @@ impl NNTPStream {
     ///
     /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
     ///     .expect("Failed to connect");
-    /// let caps = client.capabilities().expect("Failed to get capabilities");
-    /// for cap in &caps {
-    ///     println!("{}", cap);
+    /// client.group("comp.test").expect("Failed to select group");
+    /// let articles = client.article_many(&[3000, 3001, 3002]).expect("Connection failed");
+    /// for article in articles {
+    ///     match article {
+    ///         Ok(article) => println!("{:?}", article.headers.get("Subject")),
+    ///         Err(e) => eprintln!("skipped: {}", e),
+    ///     }
     /// }
     /// ```
-    pub fn capabilities(&mut self) -> Result<Vec<String>> {
-        self.send_command_expect_multiline_response(
-            "CAPABILITIES\r\n",
-            vec![ResponseCode::CapabilitiesListFollows],
-        )
-    }
-
-    /// Retrieves the server's current date and time.
-    ///
-    /// Returns the date in `YYYYMMDDHHMMSS` format as reported by the server.
-    pub fn date(&mut self) -> Result<String> {
-        self.send_command_expect_response("DATE\r\n", vec![ResponseCode::ServerDateTime])
+    pub fn article_many(&mut self, articles: &[impl Display]) -> Result<Vec<Result<Article>>> {
+        self.pipeline("ARTICLE", articles, |stream| {
+            stream
+                .read_response(vec![ResponseCode::ArticleFollows])
+                .map_err(article_unavailable)?;
+            Ok(Article::new_article(stream.read_multiline_response()?))
+        })
     }
 
-    /// Advances the current article pointer to the next article in the selected
-    /// newsgroup.
+    /// Retrieves the headers of several articles with pipelined `HEAD`
+    /// commands. See [`NNTPStream::article_many`] for how the batch works.
     ///
     /// # Errors
     ///
-    /// Returns a response error with code 421 if the current article is the last
-    /// in the group, or code 412 if no group is selected.
-    pub fn next_article(&mut self) -> Result<String> {
-        self.send_command_expect_response("NEXT\r\n", vec![ResponseCode::ArticleExistsAndSelected])
+    /// A missing article only fails its own entry, with
+    /// [`NNTPError::ArticleUnavailable`] or a response error with code 430.
+    /// The outer error is returned if the connection fails.
+    pub fn head_many(&mut self, articles: &[impl Display]) -> Result<Vec<Result<Headers>>> {
+        self.pipeline("HEAD", articles, |stream| {
+            stream
+                .read_response(vec![ResponseCode::ArticleHeadersFollows])
+                .map_err(article_unavailable)?;
+            Ok(Headers::from_lines(&stream.read_multiline_response()?))
+        })
     }
 
-    /// Moves the current article pointer to the previous article in the selected
-    /// newsgroup.
+    /// Retrieves the bodies of several articles with pipelined `BODY`
+    /// commands. See [`NNTPStream::article_many`] for how the batch works.
     ///
     /// # Errors
     ///
-    /// Returns a response error with code 422 if the current article is the
-    /// first in the group, or code 412 if no group is selected.
-    pub fn last(&mut self) -> Result<String> {
-        self.send_command_expect_response("LAST\r\n", vec![ResponseCode::ArticleExistsAndSelected])
-    }
-
-    /// Lists all newsgroups available on the server.
+    /// A missing article only fails its own entry, with
+    /// [`NNTPError::ArticleUnavailable`] or a response error with code 430.
+    /// The outer error is returned if the connection fails.
     ///
     /// # Example
     ///
@@ -491,54 +926,456 @@ impl NNTPStream {
     ///
     /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
     ///     .expect("Failed to connect");
-    /// let groups = client.list().expect("Failed to list groups");
-    /// for group in &groups {
-    ///     println!("{}", group);
-    /// }
+    /// let ids = ["<part1@example.com>", "<part2@example.com>"];
+    /// let bodies = client.body_many(&ids).expect("Connection failed");
+    /// let missing = bodies.iter().filter(|body| body.is_err()).count();
+    /// println!("{} of {} parts missing", missing, ids.len());
     /// ```
-    pub fn list(&mut self) -> Result<Vec<NewsGroup>> {
-        match self.stream.write_all(b"LIST\r\n") {
-            Ok(_) => (),
-            Err(error) => return Err(errors::write_error_or_network(error)),
-        }
+    pub fn body_many(&mut self, articles: &[impl Display]) -> Result<Vec<Result<Vec<String>>>> {
+        self.pipeline("BODY", articles, |stream| {
+            stream
+                .read_response(vec![ResponseCode::ArticleBodyFollows])
+                .map_err(article_unavailable)?;
+            stream.read_multiline_response()
+        })
+    }
 
-        match self.read_response(vec![ResponseCode::InformationFollows]) {
-            Ok(_) => (),
-            Err(e) => return Err(e),
-        }
+    /// Checks that several articles exist with pipelined `STAT` commands,
+    /// returning each response message (`n message-id`). See
+    /// [`NNTPStream::article_many`] for how the batch works.
+    ///
+    /// # Errors
+    ///
+    /// A missing article only fails its own entry, with
+    /// [`NNTPError::ArticleUnavailable`] or a response error with code 430.
+    /// The outer error is returned if the connection fails.
+    pub fn stat_many(&mut self, articles: &[impl Display]) -> Result<Vec<Result<String>>> {
+        self.pipeline("STAT", articles, |stream| {
+            let (_, message) = stream
+                .read_response(vec![ResponseCode::ArticleExistsAndSelected])
+                .map_err(article_unavailable)?;
+            Ok(message)
+        })
+    }
 
-        match self.read_multiline_response() {
-            Ok(lines) => {
-                let lines: Vec<NewsGroup> = lines
-                    .iter()
-                    .map(|s| NewsGroup::from_list_response(s))
-                    .collect();
-                Ok(lines)
+    /// Retrieves an article, given by message ID or by number in the currently
+    /// selected newsgroup, as lines read from the server on demand.
+    ///
+    /// Unlike [`NNTPStream::raw_article_by_number`], the article is never held
+    /// in memory as a whole. See [`ResponseLines`].
+    ///
+    /// Dropping the iterator early does not save the download: the rest of
+    /// the article is still read from the server and discarded, so that the
+    /// connection stays usable.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ArticleUnavailable`] if no article exists with that
+    /// number, or a response error with code 430 if the message ID is not found.
+    pub fn article_stream(&mut self, article: impl Display) -> Result<ResponseLines<'_>> {
+        self.write_command(format!("ARTICLE {}\r\n", article).as_bytes())?;
+        match self.read_response(vec![ResponseCode::ArticleFollows]) {
+            Ok(_) => Ok(ResponseLines::new(self)),
+            Err(NNTPError::ResponseCode { received: 423, .. }) => {
+                Err(NNTPError::ArticleUnavailable)
             }
             Err(e) => Err(e),
         }
     }
 
-    /// Selects a newsgroup as the currently active group.
-    ///
-    /// After selecting a group, article retrieval commands (`article`, `body`,
-    /// `head`, `stat`) operate relative to this group's article numbers.
+    /// Retrieves the headers of an article as lines read from the server on
+    /// demand. See [`NNTPStream::article_stream`].
     ///
-    /// # Example
+    /// # Errors
     ///
-    /// ```no_run
-    /// use nntp::NNTPStream;
+    /// Returns a response error with code 423 or 430 if the article is not found.
+    pub fn head_stream(&mut self, article: impl Display) -> Result<ResponseLines<'_>> {
+        self.send_command_expect_stream(
+            &format!("HEAD {}\r\n", article),
+            vec![ResponseCode::ArticleHeadersFollows],
+        )
+    }
+
+    /// Retrieves the body of an article as lines read from the server on
+    /// demand. See [`NNTPStream::article_stream`].
     ///
-    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
-    ///     .expect("Failed to connect");
-    /// let group = client.group("comp.test")
-    ///     .expect("Failed to select group");
-    /// println!("{} articles in {}", group.number, group.name);
-    /// ```
+    /// As there, dropping the iterator early still reads the rest of the body
+    /// from the server.
     ///
     /// # Errors
     ///
-    /// Returns a response error with code 411 if the newsgroup does not exist.
+    /// Returns a response error with code 423 or 430 if the article is not found.
+    ///
+    /// # Example
+    ///
+    /// See [`ResponseLines`].
+    pub fn body_stream(&mut self, article: impl Display) -> Result<ResponseLines<'_>> {
+        self.send_command_expect_stream(
+            &format!("BODY {}\r\n", article),
+            vec![ResponseCode::ArticleBodyFollows],
+        )
+    }
+
+    /// Retrieves an article, given by message ID or by number in the currently
+    /// selected newsgroup, as the bytes sent by the server.
+    ///
+    /// No character set decoding is done, so binary posts (e.g. yEnc) come
+    /// through intact and [`NNTPError::DecodingError`] cannot occur. Lines keep
+    /// their `\r\n` endings; they are dot-unstuffed unless
+    /// [raw mode](NNTPStream::set_raw_mode) is on, and the terminating `.` line
+    /// is not included.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ArticleUnavailable`] if no article exists with that
+    /// number, or a response error with code 430 if the message ID is not found.
+    pub fn article_bytes(&mut self, article: impl Display) -> Result<Vec<u8>> {
+        self.write_command(format!("ARTICLE {}\r\n", article).as_bytes())?;
+        match self.read_response(vec![ResponseCode::ArticleFollows]) {
+            Ok(_) => self.read_multiline_bytes(),
+            Err(NNTPError::ResponseCode { received: 423, .. }) => {
+                Err(NNTPError::ArticleUnavailable)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Retrieves the headers of an article as the bytes sent by the server.
+    /// See [`NNTPStream::article_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 423 or 430 if the article is not found.
+    pub fn head_bytes(&mut self, article: impl Display) -> Result<Vec<u8>> {
+        self.write_command(format!("HEAD {}\r\n", article).as_bytes())?;
+        self.read_response(vec![ResponseCode::ArticleHeadersFollows])?;
+        self.read_multiline_bytes()
+    }
+
+    /// Retrieves the body of an article as the bytes sent by the server.
+    /// See [`NNTPStream::article_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 423 or 430 if the article is not found.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::fs;
+    /// use nntp::NNTPStream;
+    ///
+    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    /// let body = client.body_bytes("<part1of3@example.com>").expect("No such article");
+    /// fs::write("part1.yenc", &body).expect("Failed to save");
+    /// ```
+    pub fn body_bytes(&mut self, article: impl Display) -> Result<Vec<u8>> {
+        self.write_command(format!("BODY {}\r\n", article).as_bytes())?;
+        self.read_response(vec![ResponseCode::ArticleBodyFollows])?;
+        self.read_multiline_bytes()
+    }
+}
+
+/// Information and listing commands (RFC 3977 §7)
+impl NNTPStream {
+    /// Retrieves the capabilities supported by the server.
+    ///
+    /// The result is cached until it may have changed, as required by
+    /// RFC 3977: it is discarded after `MODE READER`, [`NNTPStream::starttls`],
+    /// successful authentication and [`NNTPStream::re_connect`]. Other methods
+    /// use it to pick commands, e.g. `OVER` or `XOVER`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::NNTPStream;
+    ///
+    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    /// let caps = client.capabilities().expect("Failed to get capabilities");
+    /// if caps.over {
+    ///     println!("OVER is supported");
+    /// }
+    /// for line in &caps.lines {
+    ///     println!("{}", line);
+    /// }
+    /// ```
+    pub fn capabilities(&mut self) -> Result<Capabilities> {
+        if let Some(capabilities) = &self.capabilities {
+            return Ok(capabilities.clone());
+        }
+
+        let lines = self.send_command_expect_multiline_response(
+            "CAPABILITIES\r\n",
+            vec![ResponseCode::CapabilitiesListFollows],
+        )?;
+        let capabilities = Capabilities::from_capabilities_response(&lines);
+        self.capabilities = Some(capabilities.clone());
+        Ok(capabilities)
+    }
+
+    /// Retrieves the server's current date and time, in UTC.
+    ///
+    /// This is the clock to use for [`NNTPStream::newgroups`] and
+    /// [`NNTPStream::newnews`], as the local clock may differ from the
+    /// server's.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::ParseError`] if the server's date cannot be parsed.
+    pub fn date(&mut self) -> Result<DateTime> {
+        let date =
+            self.send_command_expect_response("DATE\r\n", vec![ResponseCode::ServerDateTime])?;
+        DateTime::parse_nntp(&date)
+    }
+
+    /// Advances the current article pointer to the next article in the selected
+    /// newsgroup.
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 421 if the current article is the last
+    /// in the group, or code 412 if no group is selected.
+    pub fn next_article(&mut self) -> Result<String> {
+        self.send_command_expect_response("NEXT\r\n", vec![ResponseCode::ArticleExistsAndSelected])
+    }
+
+    /// Moves the current article pointer to the previous article in the selected
+    /// newsgroup.
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 422 if the current article is the
+    /// first in the group, or code 412 if no group is selected.
+    pub fn last(&mut self) -> Result<String> {
+        self.send_command_expect_response("LAST\r\n", vec![ResponseCode::ArticleExistsAndSelected])
+    }
+
+    /// Lists all newsgroups available on the server.
+    ///
+    /// Rows that cannot be parsed are logged and skipped;
+    /// [`NNTPStream::list_stream`] reports them instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::NNTPStream;
+    ///
+    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    /// let groups = client.list().expect("Failed to list groups");
+    /// for group in &groups {
+    ///     println!("{}", group);
+    /// }
+    /// ```
+    pub fn list(&mut self) -> Result<Vec<NewsGroup>> {
+        match self.stream.write_all(b"LIST\r\n") {
+            Ok(_) => (),
+            Err(error) => return Err(errors::write_error_or_network(error)),
+        }
+
+        match self.read_response(vec![ResponseCode::InformationFollows]) {
+            Ok(_) => (),
+            Err(e) => return Err(e),
+        }
+
+        match self.read_multiline_response() {
+            Ok(lines) => Ok(parse_list_rows(&lines, NewsGroup::from_list_response)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Lists all newsgroups available on the server, parsing each one as it
+    /// is read instead of collecting the whole active file first.
+    ///
+    /// The connection is borrowed until the iterator is exhausted or dropped;
+    /// see [`ResponseLines`]. A row that cannot be parsed is returned as an
+    /// [`NNTPError::ParseError`], and the listing continues after it.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::NNTPStream;
+    ///
+    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    /// let binaries = client
+    ///     .list_stream()
+    ///     .expect("Failed to list groups")
+    ///     .filter_map(|group| group.ok())
+    ///     .filter(|group| group.name.starts_with("alt.binaries."))
+    ///     .count();
+    /// println!("{} binary groups", binaries);
+    /// ```
+    pub fn list_stream(&mut self) -> Result<impl Iterator<Item = Result<NewsGroup>> + '_> {
+        let lines =
+            self.send_command_expect_stream("LIST\r\n", vec![ResponseCode::InformationFollows])?;
+        Ok(lines.map(|line| line.and_then(|line| NewsGroup::from_list_response(&line))))
+    }
+
+    /// Lists the newsgroups matching `wildmat`, using `LIST ACTIVE`.
+    ///
+    /// With no `wildmat`, this is equivalent to [`NNTPStream::list`]. Passing a
+    /// pattern (e.g. `"comp.lang.*"`) avoids transferring the whole active file.
+    /// Rows that cannot be parsed are logged and skipped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::NNTPStream;
+    ///
+    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    /// let groups = client.list_active(Some("comp.lang.*")).expect("Failed to list groups");
+    /// for group in &groups {
+    ///     println!("{}", group);
+    /// }
+    /// ```
+    pub fn list_active(&mut self, wildmat: Option<&str>) -> Result<Vec<NewsGroup>> {
+        let lines = self.list_keyword("ACTIVE", wildmat)?;
+        Ok(parse_list_rows(&lines, NewsGroup::from_list_response))
+    }
+
+    /// Lists the names and descriptions of the newsgroups matching `wildmat`,
+    /// using `LIST NEWSGROUPS`. Rows that cannot be parsed are logged and
+    /// skipped.
+    pub fn list_newsgroups(&mut self, wildmat: Option<&str>) -> Result<Vec<NewsGroupDescription>> {
+        let lines = self.list_keyword("NEWSGROUPS", wildmat)?;
+        Ok(parse_list_rows(
+            &lines,
+            NewsGroupDescription::from_list_response,
+        ))
+    }
+
+    /// Lists when and by whom the newsgroups matching `wildmat` were created,
+    /// using `LIST ACTIVE.TIMES`. Rows that cannot be parsed are logged and
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 503 if the server does not keep this
+    /// information.
+    pub fn list_active_times(&mut self, wildmat: Option<&str>) -> Result<Vec<GroupCreation>> {
+        let lines = self.list_keyword("ACTIVE.TIMES", wildmat)?;
+        Ok(parse_list_rows(&lines, GroupCreation::from_list_response))
+    }
+
+    /// Lists the header fields and metadata items that can be retrieved with
+    /// [`NNTPStream::hdr`], using `LIST HEADERS`.
+    ///
+    /// Some servers support different fields depending on whether `HDR` is
+    /// given a message ID or a range; pass [`ListHeadersVariant`] to ask about
+    /// one form only. A returned `":"` means any header may be requested.
+    pub fn list_headers(&mut self, variant: Option<ListHeadersVariant>) -> Result<Vec<String>> {
+        let argument = variant.map(|v| match v {
+            ListHeadersVariant::MessageId => "MSGID",
+            ListHeadersVariant::Range => "RANGE",
+        });
+        let chars_to_trim: &[char] = &['\r', '\n', ' '];
+        Ok(self
+            .list_keyword("HEADERS", argument)?
+            .iter()
+            .map(|s| s.trim_matches(chars_to_trim).to_string())
+            .collect())
+    }
+
+    /// Lists the rules for choosing a `Distribution` header, using
+    /// `LIST DISTRIB.PATS`. Rows that cannot be parsed are logged and skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 503 if the server does not keep this
+    /// information.
+    pub fn list_distrib_pats(&mut self) -> Result<Vec<DistributionPattern>> {
+        let lines = self.list_keyword("DISTRIB.PATS", None)?;
+        Ok(parse_list_rows(
+            &lines,
+            DistributionPattern::from_list_response,
+        ))
+    }
+
+    /// Lists the newsgroups matching `wildmat` with their exact article
+    /// counts, using `LIST COUNTS` (RFC 6048).
+    ///
+    /// Unlike [`NNTPStream::list_active`], the returned groups have
+    /// [`NewsGroup::count`] set, which accounts for gaps in the article numbers.
+    /// Rows that cannot be parsed are logged and skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 503 if the server does not support
+    /// the keyword.
+    pub fn list_counts(&mut self, wildmat: Option<&str>) -> Result<Vec<NewsGroup>> {
+        let lines = self.list_keyword("COUNTS", wildmat)?;
+        Ok(parse_list_rows(&lines, NewsGroup::from_counts_response))
+    }
+
+    /// Lists the values the server recognizes in the `Distribution` header,
+    /// using `LIST DISTRIBUTIONS` (RFC 6048). Rows that cannot be parsed are
+    /// logged and skipped.
+    pub fn list_distributions(&mut self) -> Result<Vec<Distribution>> {
+        let lines = self.list_keyword("DISTRIBUTIONS", None)?;
+        Ok(parse_list_rows(&lines, Distribution::from_list_response))
+    }
+
+    /// Lists the submission address templates for moderated newsgroups, using
+    /// `LIST MODERATORS` (RFC 6048). Rows that cannot be parsed are logged and
+    /// skipped.
+    pub fn list_moderators(&mut self) -> Result<Vec<ModeratorTemplate>> {
+        let lines = self.list_keyword("MODERATORS", None)?;
+        Ok(parse_list_rows(
+            &lines,
+            ModeratorTemplate::from_list_response,
+        ))
+    }
+
+    /// Retrieves the server's message of the day, using `LIST MOTD` (RFC 6048).
+    ///
+    /// Returns the text lines without their `\r\n` terminators.
+    pub fn list_motd(&mut self) -> Result<Vec<String>> {
+        let chars_to_trim: &[char] = &['\r', '\n'];
+        Ok(self
+            .list_keyword("MOTD", None)?
+            .iter()
+            .map(|s| s.trim_end_matches(chars_to_trim).to_string())
+            .collect())
+    }
+
+    /// Lists the newsgroups the server recommends new readers subscribe to,
+    /// using `LIST SUBSCRIPTIONS` (RFC 6048).
+    pub fn list_subscriptions(&mut self, wildmat: Option<&str>) -> Result<Vec<String>> {
+        let chars_to_trim: &[char] = &['\r', '\n', ' '];
+        Ok(self
+            .list_keyword("SUBSCRIPTIONS", wildmat)?
+            .iter()
+            .map(|s| s.trim_matches(chars_to_trim).to_string())
+            .filter(|s| !s.is_empty())
+            .collect())
+    }
+
+    /// Selects a newsgroup as the currently active group.
+    ///
+    /// After selecting a group, article retrieval commands (`article`, `body`,
+    /// `head`, `stat`) operate relative to this group's article numbers.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::NNTPStream;
+    ///
+    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    /// let group = client.group("comp.test")
+    ///     .expect("Failed to select group");
+    /// println!("{} articles in {}", group.number, group.name);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 411 if the newsgroup does not exist.
+    /// Returns [`NNTPError::ParseError`] if the server's response cannot be
+    /// parsed.
     pub fn group(&mut self, group: &str) -> Result<NewsGroup> {
         let group_command = format!("GROUP {}\r\n", group);
 
@@ -548,11 +1385,108 @@ impl NNTPStream {
         }
 
         match self.read_response(vec![ResponseCode::ArticleNumbersFollows]) {
-            Ok((_, res)) => Ok(NewsGroup::from_group_response(&res)),
+            Ok((_, res)) => {
+                let group = NewsGroup::from_group_response(&res)?;
+                self.current_group = Some(group.clone());
+                Ok(group)
+            }
             Err(e) => Err(e),
         }
     }
 
+    /// Selects a newsgroup and lists the numbers of the articles it contains,
+    /// using `LISTGROUP`.
+    ///
+    /// Unlike [`NNTPStream::group`], which only reports the low and high
+    /// watermarks, this returns the article numbers that actually exist, so
+    /// gaps left by expired or cancelled articles can be skipped. When `range`
+    /// is given, only article numbers within it are listed.
+    ///
+    /// On success, the group becomes the currently selected group.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::NNTPStream;
+    ///
+    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    /// let numbers = client.listgroup("comp.test", Some((3000..).into()))
+    ///     .expect("Failed to list group");
+    /// for number in numbers {
+    ///     let _ = client.article_by_number(number);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 411 if the newsgroup does not exist.
+    pub fn listgroup(&mut self, group: &str, range: Option<ArticleRange>) -> Result<Vec<isize>> {
+        let listgroup_command = match range {
+            Some(range) => format!("LISTGROUP {} {}\r\n", group, range),
+            None => format!("LISTGROUP {}\r\n", group),
+        };
+
+        match self.stream.write_all(listgroup_command.as_bytes()) {
+            Ok(_) => (),
+            Err(error) => return Err(errors::write_error_or_network(error)),
+        }
+
+        let (_, res) = self.read_response(vec![ResponseCode::ArticleNumbersFollows])?;
+        // the article numbers follow regardless, so a bad status line only
+        // leaves the group unknown
+        self.current_group = match NewsGroup::from_group_response(&res) {
+            Ok(group) => Some(group),
+            Err(e) => {
+                log::warn!("Ignoring LISTGROUP status line: {}", e);
+                None
+            }
+        };
+
+        let chars_to_trim: &[char] = &['\r', '\n', ' '];
+        self.read_multiline_response()?
+            .iter()
+            .map(|line| {
+                line.trim_matches(chars_to_trim)
+                    .parse::<isize>()
+                    .map_err(|_| NNTPError::InvalidResponse {
+                        response: line.to_string(),
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns the currently selected newsgroup, as reported by the last
+    /// successful [`NNTPStream::group`] or [`NNTPStream::listgroup`] call.
+    ///
+    /// This is cleared by [`NNTPStream::re_connect`], since a new connection
+    /// starts with no group selected.
+    pub fn current_group(&self) -> Option<&NewsGroup> {
+        self.current_group.as_ref()
+    }
+
+    /// Retrieves the order of fields in the overview database, using
+    /// `LIST OVERVIEW.FMT`.
+    ///
+    /// The result is cached for the lifetime of the connection, so the server
+    /// is only asked once. [`NNTPStream::over`] uses it to map each overview
+    /// field to its header name.
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 503 if the server does not support
+    /// the command.
+    pub fn list_overview_fmt(&mut self) -> Result<OverviewFormat> {
+        if let Some(format) = &self.overview_fmt {
+            return Ok(format.clone());
+        }
+
+        let lines = self.list_keyword("OVERVIEW.FMT", None)?;
+        let format = OverviewFormat::from_list_response(&lines);
+        self.overview_fmt = Some(format.clone());
+        Ok(format)
+    }
+
     /// Retrieves the server's help text.
     ///
     /// Returns a multi-line help string describing available commands.
@@ -562,18 +1496,27 @@ impl NNTPStream {
 
     /// Retrieves a list of newsgroups created since the given date and time.
     ///
-    /// The `date` should be in `YYMMDD` format and `time` in `HHMMSS` format.
-    /// Set `use_gmt` to `true` to interpret the time as GMT.
+    /// The date is sent in GMT with a four-digit year, see
+    /// [`DateTime::to_nntp`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::{DateTime, NNTPStream};
+    ///
+    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    /// let now = client.date().expect("Failed to get the server's date");
+    /// let last_week = DateTime::from_timestamp(now.timestamp() - 7 * 86400);
+    /// let groups = client.newgroups(last_week).expect("Failed to list new groups");
+    /// ```
     ///
     /// # Errors
     ///
     /// Returns [`NNTPError::ResponseCode`] if the server does not support the
-    /// NEWGROUPS command or the date format is invalid.
-    pub fn newgroups(&mut self, date: &str, time: &str, use_gmt: bool) -> Result<Vec<String>> {
-        let newgroups_command = match use_gmt {
-            true => format!("NEWGROUPS {} {} GMT\r\n", date, time),
-            false => format!("NEWGROUPS {} {}\r\n", date, time),
-        };
+    /// NEWGROUPS command.
+    pub fn newgroups(&mut self, since: DateTime) -> Result<Vec<String>> {
+        let newgroups_command = format!("NEWGROUPS {} GMT\r\n", since.to_nntp());
 
         self.send_command_expect_multiline_response(
             &newgroups_command,
@@ -584,20 +1527,10 @@ impl NNTPStream {
     /// Retrieves a list of new articles posted since the given date and time
     /// in the newsgroups matching the `wildmat` pattern.
     ///
-    /// The `wildmat` is a wildcard pattern (e.g. `"comp.*"`). The `date` should
-    /// be in `YYMMDD` format and `time` in `HHMMSS` format.
-    /// Set `use_gmt` to `true` to interpret the time as GMT.
-    pub fn newnews(
-        &mut self,
-        wildmat: &str,
-        date: &str,
-        time: &str,
-        use_gmt: bool,
-    ) -> Result<Vec<String>> {
-        let newnews_command = match use_gmt {
-            true => format!("NEWNEWS {} {} {} GMT\r\n", wildmat, date, time),
-            false => format!("NEWNEWS {} {} {}\r\n", wildmat, date, time),
-        };
+    /// The `wildmat` is a wildcard pattern (e.g. `"comp.*"`). The date is sent
+    /// in GMT with a four-digit year, see [`DateTime::to_nntp`].
+    pub fn newnews(&mut self, wildmat: &str, since: DateTime) -> Result<Vec<String>> {
+        let newnews_command = format!("NEWNEWS {} {} GMT\r\n", wildmat, since.to_nntp());
 
         self.send_command_expect_multiline_response(
             &newnews_command,
@@ -645,13 +1578,17 @@ impl NNTPStream {
     /// requested mode.
     pub fn set_mode(&mut self, mode: &str) -> Result<String> {
         let mode_upper = mode.to_uppercase();
-        self.send_command_expect_response(
+        let response = self.send_command_expect_response(
             &format!("MODE {}\r\n", mode_upper),
             vec![
                 ResponseCode::ServiceAvailablePostingAllowed,
                 ResponseCode::ServiceAvailablePostingProhibited,
             ],
-        )
+        )?;
+        if mode_upper == "READER" {
+            self.discard_capabilities();
+        }
+        Ok(response)
     }
 
     /// Sends `MODE READER` to indicate the client is a news reader.
@@ -659,13 +1596,16 @@ impl NNTPStream {
     /// This is typically issued before authentication. The server responds
     /// with code 200 (posting allowed) or 201 (posting prohibited).
     pub fn set_mode_reader(&mut self) -> Result<String> {
-        self.send_command_expect_response(
+        let response = self.send_command_expect_response(
             "MODE READER\r\n",
             vec![
                 ResponseCode::ServiceAvailablePostingAllowed,
                 ResponseCode::ServiceAvailablePostingProhibited,
             ],
-        )
+        )?;
+        // the server may now advertise reader commands it did not before
+        self.discard_capabilities();
+        Ok(response)
     }
 
     /// Sends `MODE POSTER` to indicate the client intends to post articles.
@@ -683,6 +1623,174 @@ impl NNTPStream {
     }
 }
 
+/// The form of `HDR` request to ask about in [`NNTPStream::list_headers`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListHeadersVariant {
+    /// Fields available when `HDR` is given a message ID (`LIST HEADERS MSGID`).
+    MessageId,
+    /// Fields available when `HDR` is given a range (`LIST HEADERS RANGE`).
+    Range,
+}
+
+/// A non-standard extension for compressed overview data, enabled with
+/// [`NNTPStream::enable_overview_compression`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverviewCompression {
+    /// `XFEATURE COMPRESS GZIP`: overview and header responses are sent as a
+    /// zlib stream.
+    Gzip,
+    /// `XFEATURE COMPRESS GZIP TERMINATOR`: as [`OverviewCompression::Gzip`],
+    /// with a `.` line after the compressed data.
+    GzipTerminator,
+    /// `XZVER`: overview data is DEFLATE-compressed and yEnc-encoded.
+    Xzver,
+}
+
+/// Overview commands (RFC 3977 §8)
+impl NNTPStream {
+    /// Enables a compressed overview extension offered by many commercial
+    /// providers that lack `COMPRESS DEFLATE`.
+    ///
+    /// Once enabled, [`NNTPStream::over`] and [`NNTPStream::hdr`] transparently
+    /// inflate compressed responses, so callers see the same data as
+    /// without compression. The extension is enabled again after
+    /// [`NNTPStream::re_connect`]. If the server turns out not to know `XZVER`,
+    /// plain `OVER`/`XOVER` is used instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error if the server rejects `XFEATURE`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::{NNTPStream, OverviewCompression};
+    ///
+    /// let mut client = NNTPStream::connect("nntps://news.example.com".to_string())
+    ///     .expect("Failed to connect");
+    /// client.user_password_authenticate("user", "password")
+    ///     .expect("Authentication failed");
+    /// client.enable_overview_compression(OverviewCompression::Gzip)
+    ///     .expect("XFEATURE refused");
+    /// client.group("alt.binaries.test").expect("Failed to select group");
+    /// let entries = client.over(1000..=2000).expect("Failed to get overview");
+    /// ```
+    pub fn enable_overview_compression(&mut self, compression: OverviewCompression) -> Result<()> {
+        let command = match compression {
+            OverviewCompression::Gzip => Some("XFEATURE COMPRESS GZIP\r\n"),
+            OverviewCompression::GzipTerminator => Some("XFEATURE COMPRESS GZIP TERMINATOR\r\n"),
+            OverviewCompression::Xzver => None,
+        };
+        if let Some(command) = command {
+            self.send_command_expect_response(command, vec![ResponseCode::FeatureEnabled])?;
+        }
+
+        self.overview_compression = Some(compression);
+        Ok(())
+    }
+
+    /// Retrieves the overview information for a range of articles in the
+    /// currently selected newsgroup.
+    ///
+    /// `OVER` is tried first. If the server does not recognize it, the
+    /// RFC 2980 `XOVER` command is used instead, for this and all later calls.
+    /// Fields are mapped using the server's [`NNTPStream::list_overview_fmt`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::NNTPStream;
+    ///
+    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    /// client.group("comp.test").expect("Failed to select group");
+    /// for entry in client.over(3000..=3100).expect("Failed to fetch overview") {
+    ///     println!("{} {}", entry.number, entry.subject);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 423 if the range contains no articles,
+    /// or code 412 if no group is selected.
+    pub fn over(&mut self, range: impl Into<ArticleRange>) -> Result<Vec<OverviewEntry>> {
+        self.retrieve_overview(&range.into().to_string())
+    }
+
+    /// Retrieves the overview information for the article identified by the
+    /// given message ID.
+    ///
+    /// The returned entry has an article number of `0`, as required by RFC 3977.
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 430 if the message ID is not found.
+    pub fn over_by_id(&mut self, article_id: &str) -> Result<OverviewEntry> {
+        match self.retrieve_overview(article_id)?.into_iter().next() {
+            Some(entry) => Ok(entry),
+            None => Err(NNTPError::InvalidResponse {
+                response: "empty overview response".to_owned(),
+            }),
+        }
+    }
+
+    /// Retrieves a single header field for a range of articles in the
+    /// currently selected newsgroup.
+    ///
+    /// Returns `(article_number, value)` pairs. The `field` is a header name
+    /// (e.g. `"Subject"`) or one of the metadata items `":bytes"` and `":lines"`.
+    ///
+    /// `HDR` is tried first. If the server does not recognize it, the
+    /// RFC 2980 `XHDR` command is used instead, for this and all later calls.
+    /// Since `XHDR` has no metadata items, `":bytes"` and `":lines"` are then
+    /// served from the overview database.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::NNTPStream;
+    ///
+    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    /// client.group("comp.test").expect("Failed to select group");
+    /// for (number, message_id) in client.hdr("Message-ID", 3000..).expect("HDR failed") {
+    ///     println!("{} {}", number, message_id);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 423 if the range contains no articles,
+    /// or code 412 if no group is selected.
+    pub fn hdr(
+        &mut self,
+        field: &str,
+        range: impl Into<ArticleRange>,
+    ) -> Result<Vec<(isize, String)>> {
+        self.retrieve_hdr(field, &range.into().to_string())
+    }
+
+    /// Retrieves a single header field for the article identified by the given
+    /// message ID.
+    ///
+    /// The metadata items `":bytes"` and `":lines"` need `HDR`, as neither
+    /// `XHDR` nor `XOVER` has a way to look them up by message ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 430 if the message ID is not found.
+    /// Returns [`NNTPError::Unsupported`] for a metadata item if the server
+    /// lacks `HDR`.
+    pub fn hdr_by_id(&mut self, field: &str, article_id: &str) -> Result<String> {
+        match self.retrieve_hdr(field, article_id)?.into_iter().next() {
+            Some((_, value)) => Ok(value),
+            None => Err(NNTPError::InvalidResponse {
+                response: "empty header response".to_owned(),
+            }),
+        }
+    }
+}
+
 /// Authentication commands (RFC 4643)
 impl NNTPStream {
     /// Sends `AUTHINFO USER` to the server.
@@ -700,17 +1808,103 @@ impl NNTPStream {
         )
     }
 
-    /// Sends `AUTHINFO PASS` to the server.
-    ///
-    /// Expects [`ResponseCode::AuthenticationAccepted`] (281) on success.
-    fn auth_password(&mut self, password: &str) -> Result<String> {
-        self.send_command_expect_response(
-            &format!("AUTHINFO PASS {}\r\n", password),
-            vec![ResponseCode::AuthenticationAccepted],
-        )
+    /// Sends `AUTHINFO PASS` to the server.
+    ///
+    /// Expects [`ResponseCode::AuthenticationAccepted`] (281) on success.
+    fn auth_password(&mut self, password: &str) -> Result<String> {
+        self.send_command_expect_response(
+            &format!("AUTHINFO PASS {}\r\n", password),
+            vec![ResponseCode::AuthenticationAccepted],
+        )
+    }
+
+    /// Runs an `AUTHINFO SASL` exchange with the given mechanism.
+    fn sasl_exchange(&mut self, mechanism: &mut dyn SaslMechanism) -> Result<()> {
+        let channel_binding = self.channel_binding()?;
+        let initial_response = mechanism.start(channel_binding.as_ref())?;
+        let sasl_command = match initial_response {
+            Some(response) => format!(
+                "AUTHINFO SASL {} {}\r\n",
+                mechanism.name(),
+                encode_sasl_response(&response)
+            ),
+            None => format!("AUTHINFO SASL {}\r\n", mechanism.name()),
+        };
+
+        let expected_code = vec![
+            ResponseCode::AuthenticationAccepted,
+            ResponseCode::AuthenticationAcceptedWithData,
+            ResponseCode::SASLContinue,
+        ];
+        match self.stream.write_all(sasl_command.as_bytes()) {
+            Ok(_) => (),
+            Err(error) => return Err(errors::write_error_or_network(error)),
+        }
+        let (mut code, mut message) = self.read_response(expected_code.clone())?;
+
+        while code == ResponseCode::SASLContinue.into() {
+            let response = match sasl::base64_decode(&message)
+                .and_then(|challenge| mechanism.step(&challenge))
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    self.cancel_sasl();
+                    return Err(e);
+                }
+            };
+
+            let response_line = format!("{}\r\n", encode_sasl_response(&response));
+            match self.stream.write_all(response_line.as_bytes()) {
+                Ok(_) => (),
+                Err(error) => return Err(errors::write_error_or_network(error)),
+            }
+            (code, message) = self.read_response(expected_code.clone())?;
+        }
+
+        if code == ResponseCode::AuthenticationAcceptedWithData.into() {
+            mechanism.finish(Some(&sasl::base64_decode(&message)?))
+        } else {
+            mechanism.finish(None)
+        }
+    }
+
+    /// Aborts an `AUTHINFO SASL` exchange; the server answers with 481.
+    fn cancel_sasl(&mut self) {
+        if let Err(e) =
+            self.send_command_expect_response("*\r\n", vec![ResponseCode::AuthenticationFailed])
+        {
+            log::warn!("Cancelling SASL exchange failed: {}", e);
+        }
     }
 
-    // TODO: implement SASL ?
+    /// Returns the `tls-exporter` channel binding (RFC 9266) of a TLS
+    /// connection, along with the SASL mechanisms the server advertises.
+    ///
+    /// `tls-exporter` is only safe on TLS 1.3 (RFC 9266 §3), so older
+    /// connections have no channel binding.
+    fn channel_binding(&mut self) -> Result<Option<ChannelBinding>> {
+        let data = match self
+            .stream
+            .get_ref()
+            .tls_connection()
+            .filter(|tls| tls.protocol_version() == Some(rustls::ProtocolVersion::TLSv1_3))
+            .and_then(|tls| {
+                tls.export_keying_material([0u8; 32], b"EXPORTER-Channel-Binding", None)
+                    .ok()
+            }) {
+            Some(data) => data.to_vec(),
+            None => return Ok(None),
+        };
+        let server_mechanisms = self
+            .supported_capabilities()?
+            .map(|capabilities| capabilities.sasl.clone())
+            .unwrap_or_default();
+        Ok(Some(ChannelBinding {
+            kind: "tls-exporter",
+            data,
+            server_mechanisms,
+        }))
+    }
 }
 
 /// Posting commands (RFC 3977 §5)
@@ -718,7 +1912,9 @@ impl NNTPStream {
     /// Posts a message to the currently selected newsgroup.
     ///
     /// The `message` must be a complete article including headers and body,
-    /// terminated with `\r\n.\r\n` (a line containing only a dot).
+    /// already dot-stuffed and terminated with `\r\n.\r\n` (a line
+    /// containing only a dot). Use [`NNTPStream::post_article`] to have this
+    /// done for you.
     ///
     /// # Message format
     ///
@@ -777,6 +1973,161 @@ impl NNTPStream {
             Err(e) => Err(e),
         }
     }
+
+    /// Posts an article given as plain content, without the terminating `.`
+    /// line.
+    ///
+    /// Line endings are normalized to `\r\n`, lines that begin with a dot are
+    /// dot-stuffed and the terminator is appended, see
+    /// [`encode_data_block`](crate::lines::encode_data_block).
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error with code 440 if the server does not allow posting.
+    /// Returns a response error with code 441 if the server rejects the message content.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::NNTPStream;
+    ///
+    /// let mut client = NNTPStream::connect("nntp.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    ///
+    /// let article = "From: user@example.com\n\
+    ///                Newsgroups: comp.test\n\
+    ///                Subject: Test post\n\
+    ///                \n\
+    ///                .signature lines are fine too\n";
+    /// client.post_article(article).expect("Failed to post");
+    /// ```
+    pub fn post_article(&mut self, content: &str) -> Result<()> {
+        self.post(&lines::encode_data_block(content))
+    }
+
+    /// Offers an article to the server with `IHAVE`, as a peer would in a
+    /// news feed (RFC 3977 §6.3.2).
+    ///
+    /// The article is only sent if the server wants it. Like with
+    /// [`NNTPStream::post`], the `article` must be complete, dot-stuffed and
+    /// terminated with `\r\n.\r\n`; [`NNTPStream::ihave_article`] takes
+    /// plain content instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::InvalidMessage`] if the article does not end with
+    /// the required `\r\n.\r\n` terminator.
+    /// Returns a response error for any other response, e.g. code 502 if the
+    /// server does not accept feeds from this client.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nntp::{IhaveOutcome, NNTPStream};
+    ///
+    /// let mut client = NNTPStream::connect("peer.example.com:119".to_string())
+    ///     .expect("Failed to connect");
+    ///
+    /// let article = "Path: news.example.com!not-for-mail\r\n\
+    ///                Message-ID: <i.am.an.article@example.com>\r\n\
+    ///                From: user@example.com\r\n\
+    ///                Newsgroups: comp.test\r\n\
+    ///                Subject: Test\r\n\
+    ///                \r\n\
+    ///                This is a test.\r\n\
+    ///                .\r\n";
+    /// match client.ihave("<i.am.an.article@example.com>", article) {
+    ///     Ok(IhaveOutcome::TryLater) => println!("queue for retry"),
+    ///     Ok(outcome) => println!("{:?}", outcome),
+    ///     Err(e) => eprintln!("feed error: {}", e),
+    /// }
+    /// ```
+    pub fn ihave(&mut self, message_id: &str, article: &str) -> Result<IhaveOutcome> {
+        if !self.is_valid_message(article) {
+            return Err(NNTPError::InvalidMessage {
+                message: article.to_owned(),
+                reason: "Invalid message format. Message must end with \"\r\n.\r\n\"".to_owned(),
+            });
+        }
+
+        match self
+            .stream
+            .write_all(format!("IHAVE {}\r\n", message_id).as_bytes())
+        {
+            Ok(_) => (),
+            Err(error) => return Err(errors::write_error_or_network(error)),
+        }
+
+        let (code, _) = self.read_response(vec![
+            ResponseCode::SendArticleToTransfer,
+            ResponseCode::ArticleNotWanted,
+            ResponseCode::TransferFailed,
+        ])?;
+        if code == ResponseCode::ArticleNotWanted.into() {
+            return Ok(IhaveOutcome::NotWanted);
+        }
+        if code == ResponseCode::TransferFailed.into() {
+            return Ok(IhaveOutcome::TryLater);
+        }
+
+        match self.stream.write_all(article.as_bytes()) {
+            Ok(_) => (),
+            Err(error) => return Err(errors::write_error_or_network(error)),
+        }
+
+        let (code, _) = self.read_response(vec![
+            ResponseCode::ArticleTransferredOK,
+            ResponseCode::TransferFailed,
+            ResponseCode::TransferRejected,
+        ])?;
+        Ok(match code {
+            code if code == ResponseCode::ArticleTransferredOK.into() => IhaveOutcome::Accepted,
+            code if code == ResponseCode::TransferFailed.into() => IhaveOutcome::TryLater,
+            _ => IhaveOutcome::Rejected,
+        })
+    }
+
+    /// Offers an article given as plain content, without the terminating `.`
+    /// line, with `IHAVE`. The content is encoded as in
+    /// [`NNTPStream::post_article`].
+    ///
+    /// # Errors
+    ///
+    /// See [`NNTPStream::ihave`].
+    pub fn ihave_article(&mut self, message_id: &str, content: &str) -> Result<IhaveOutcome> {
+        self.ihave(message_id, &lines::encode_data_block(content))
+    }
+
+    /// Switches to streaming mode with `MODE STREAM` (RFC 4644) and returns
+    /// a session that pipelines `CHECK` and `TAKETHIS`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a response error if the server does not support streaming.
+    ///
+    /// # Example
+    ///
+    /// See [`StreamingSession`].
+    pub fn mode_stream(&mut self) -> Result<StreamingSession<'_>> {
+        self.send_command_expect_response(
+            "MODE STREAM\r\n",
+            vec![ResponseCode::StreamingPermitted],
+        )?;
+        Ok(StreamingSession::new(self))
+    }
+}
+
+/// The outcome of offering an article with [`NNTPStream::ihave`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IhaveOutcome {
+    /// The article was transferred (235).
+    Accepted,
+    /// The server already has the article or does not want it (435).
+    NotWanted,
+    /// The transfer was not possible or failed; try again later (436).
+    TryLater,
+    /// The article was rejected; do not try again (437).
+    Rejected,
 }
 
 /// base protocol handling helpers
@@ -815,6 +2166,79 @@ impl NNTPStream {
         self.read_multiline_response()
     }
 
+    fn send_command_expect_stream(
+        &mut self,
+        command: &str,
+        expected_code: Vec<codes::ResponseCode>,
+    ) -> Result<ResponseLines<'_>> {
+        self.write_command(command.as_bytes())?;
+        self.read_response(expected_code)?;
+        Ok(ResponseLines::new(self))
+    }
+
+    // Like send_command_expect_multiline_response, but inflates the data when
+    // `XFEATURE COMPRESS GZIP` was enabled and the status line announces it.
+    fn send_command_expect_compressible_response(
+        &mut self,
+        command: &str,
+        expected_code: Vec<codes::ResponseCode>,
+    ) -> Result<Vec<String>> {
+        match self.stream.write_all(command.as_bytes()) {
+            Ok(_) => (),
+            Err(error) => return Err(errors::write_error_or_network(error)),
+        }
+
+        let (_, message) = self.read_response(expected_code)?;
+        let gzip = matches!(
+            self.overview_compression,
+            Some(OverviewCompression::Gzip | OverviewCompression::GzipTerminator)
+        );
+        if !gzip || !message.to_ascii_uppercase().contains("COMPRESS=GZIP") {
+            return self.read_multiline_response();
+        }
+
+        let data = compress::read_zlib_block(&mut self.stream).map_err(compression_error)?;
+        if self.overview_compression == Some(OverviewCompression::GzipTerminator) {
+            self.read_multiline_response()?;
+        }
+        decode_lines(&data, !self.raw_mode)
+    }
+
+    fn retrieve_xzver(&mut self, argument: &str) -> Result<Vec<String>> {
+        match self
+            .stream
+            .write_all(format!("XZVER {}\r\n", argument).as_bytes())
+        {
+            Ok(_) => (),
+            Err(error) => return Err(errors::write_error_or_network(error)),
+        }
+        self.read_response(vec![ResponseCode::OverviewInformationFollows])?;
+
+        // yEnc does not always escape leading dots, so unstuff even in raw mode
+        let mut encoded = self.read_multiline_raw()?;
+        if self.raw_mode {
+            for line in encoded.iter_mut() {
+                *line = lines::unstuff_line(line).to_vec();
+            }
+        }
+        let data = compress::yenc_decode(&encoded)
+            .and_then(|data| compress::inflate_raw(&data))
+            .map_err(compression_error)?;
+        decode_lines(&data, false)
+    }
+
+    fn list_keyword(&mut self, keyword: &str, argument: Option<&str>) -> Result<Vec<String>> {
+        let list_command = match argument {
+            Some(argument) => format!("LIST {} {}\r\n", keyword, argument),
+            None => format!("LIST {}\r\n", keyword),
+        };
+
+        self.send_command_expect_multiline_response(
+            &list_command,
+            vec![ResponseCode::InformationFollows],
+        )
+    }
+
     fn retrieve_article(&mut self, article_command: &str) -> Result<Article> {
         match self.stream.write_all(article_command.as_bytes()) {
             Ok(_) => (),
@@ -870,13 +2294,204 @@ impl NNTPStream {
         )
     }
 
-    fn retrieve_head(&mut self, head_command: &str) -> Result<Vec<String>> {
-        self.send_command_expect_multiline_response(
+    fn retrieve_head(&mut self, head_command: &str) -> Result<Headers> {
+        let lines = self.send_command_expect_multiline_response(
             head_command,
             vec![ResponseCode::ArticleHeadersFollows],
+        )?;
+        Ok(Headers::from_lines(&lines))
+    }
+
+    fn retrieve_overview(&mut self, argument: &str) -> Result<Vec<OverviewEntry>> {
+        let format = self.overview_format()?;
+        let lines = self.retrieve_overview_lines(argument)?;
+        lines
+            .iter()
+            .map(|l| OverviewEntry::parse_with_format(l, &format))
+            .collect()
+    }
+
+    fn retrieve_overview_lines(&mut self, argument: &str) -> Result<Vec<String>> {
+        if self.overview_compression == Some(OverviewCompression::Xzver) {
+            match self.retrieve_xzver(argument) {
+                Ok(lines) => return Ok(lines),
+                Err(NNTPError::ResponseCode { received, .. })
+                    if received == ResponseCode::UnknownCommand.into() =>
+                {
+                    log::debug!("XZVER not supported, falling back to uncompressed overview");
+                    self.overview_compression = None;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let use_over = !self.use_xover
+            && self
+                .supported_capabilities()?
+                .is_none_or(|capabilities| capabilities.over);
+
+        if use_over {
+            match self.send_command_expect_compressible_response(
+                &format!("OVER {}\r\n", argument),
+                vec![ResponseCode::OverviewInformationFollows],
+            ) {
+                Ok(lines) => return Ok(lines),
+                Err(NNTPError::ResponseCode { received, .. })
+                    if received == ResponseCode::UnknownCommand.into() =>
+                {
+                    log::debug!("OVER not supported, falling back to XOVER");
+                    self.use_xover = true;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.send_command_expect_compressible_response(
+            &format!("XOVER {}\r\n", argument),
+            vec![ResponseCode::OverviewInformationFollows],
         )
     }
 
+    fn retrieve_hdr(&mut self, field: &str, argument: &str) -> Result<Vec<(isize, String)>> {
+        let use_hdr = !self.use_xhdr
+            && self
+                .supported_capabilities()?
+                .is_none_or(|capabilities| capabilities.hdr);
+
+        if use_hdr {
+            match self.send_command_expect_compressible_response(
+                &format!("HDR {} {}\r\n", field, argument),
+                vec![ResponseCode::HeadersFollow],
+            ) {
+                Ok(lines) => return lines.iter().map(|l| parse_hdr_line(l)).collect(),
+                Err(NNTPError::ResponseCode { received, .. })
+                    if received == ResponseCode::UnknownCommand.into() =>
+                {
+                    log::debug!("HDR not supported, falling back to XHDR");
+                    self.use_xhdr = true;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        // XHDR has no metadata items, take them from the overview instead
+        if field.eq_ignore_ascii_case(":bytes") || field.eq_ignore_ascii_case(":lines") {
+            // a server without HDR is likely to only know XOVER, which takes
+            // a range but no message ID
+            if argument.starts_with('<') {
+                return Err(NNTPError::Unsupported {
+                    message: format!("{} by message ID without HDR", field),
+                });
+            }
+            let bytes = field.eq_ignore_ascii_case(":bytes");
+            return Ok(self
+                .retrieve_overview(argument)?
+                .into_iter()
+                .map(|entry| {
+                    let value = if bytes { entry.bytes } else { entry.lines };
+                    (
+                        entry.number,
+                        value.map(|v| v.to_string()).unwrap_or_default(),
+                    )
+                })
+                .collect());
+        }
+
+        // RFC 2980 XHDR responds with 221, but some servers use 225
+        let lines = self.send_command_expect_compressible_response(
+            &format!("XHDR {} {}\r\n", field, argument),
+            vec![
+                ResponseCode::ArticleHeadersFollows,
+                ResponseCode::HeadersFollow,
+            ],
+        )?;
+        // by message ID, XHDR lines start with the message ID, not a number
+        let by_message_id = argument.starts_with('<');
+        lines
+            .iter()
+            .map(|l| {
+                // some servers send "(none)" for articles without the header
+                parse_xhdr_line(l, by_message_id).map(|(number, value)| match value.as_str() {
+                    "(none)" => (number, String::new()),
+                    _ => (number, value),
+                })
+            })
+            .collect()
+    }
+
+    // Returns the cached overview format, asking the server on first use.
+    // Servers that do not support LIST OVERVIEW.FMT get the RFC 3977 default.
+    // Other failures, such as 480 before authenticating, are returned
+    // uncached, so the next call asks again.
+    fn overview_format(&mut self) -> Result<OverviewFormat> {
+        if self.overview_fmt.is_none()
+            && self
+                .supported_capabilities()?
+                .is_some_and(|capabilities| !capabilities.supports_list("OVERVIEW.FMT"))
+        {
+            let format = OverviewFormat::default();
+            self.overview_fmt = Some(format.clone());
+            return Ok(format);
+        }
+
+        match self.list_overview_fmt() {
+            Ok(format) => Ok(format),
+            Err(NNTPError::ResponseCode { received, .. })
+                if received == ResponseCode::UnknownCommand.into()
+                    || received == ResponseCode::FeatureNotSupported.into() =>
+            {
+                log::debug!(
+                    "LIST OVERVIEW.FMT failed with {}, using default format",
+                    received
+                );
+                let format = OverviewFormat::default();
+                self.overview_fmt = Some(format.clone());
+                Ok(format)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // Sends `command argument` for each argument, keeping up to
+    // PIPELINE_WINDOW commands unanswered so neither side blocks on full
+    // buffers, and reads each response with `read`.
+    //
+    // Response code errors only concern their own command. Any other error
+    // abandons the batch with responses still pending, so the connection is
+    // closed rather than have later commands read them.
+    fn pipeline<T>(
+        &mut self,
+        command: &str,
+        arguments: &[impl Display],
+        read: impl Fn(&mut Self) -> Result<T>,
+    ) -> Result<Vec<Result<T>>> {
+        let mut results = Vec::with_capacity(arguments.len());
+        let mut sent = 0;
+
+        while results.len() < arguments.len() {
+            while sent < arguments.len() && sent - results.len() < PIPELINE_WINDOW {
+                let line = format!("{} {}\r\n", command, arguments[sent]);
+                if let Err(e) = self.write_command(line.as_bytes()) {
+                    self.close_stream();
+                    return Err(e);
+                }
+                sent += 1;
+            }
+
+            match read(self) {
+                Ok(value) => results.push(Ok(value)),
+                Err(e @ (NNTPError::ResponseCode { .. } | NNTPError::ArticleUnavailable)) => {
+                    results.push(Err(e))
+                }
+                Err(e) => {
+                    self.close_stream();
+                    return Err(e);
+                }
+            }
+        }
+        Ok(results)
+    }
+
     fn retrieve_stat(&mut self, stat_command: &str) -> Result<String> {
         self.send_command_expect_response(
             stat_command,
@@ -884,7 +2499,58 @@ impl NNTPStream {
         )
     }
 
-    fn is_valid_message(&self, message: &str) -> bool {
+    // Returns the server's capabilities, reading them if needed, or `None`
+    // if the server does not support CAPABILITIES.
+    fn supported_capabilities(&mut self) -> Result<Option<&Capabilities>> {
+        if self.capabilities.is_none() && !self.capabilities_unsupported {
+            match self.capabilities() {
+                Ok(_) => (),
+                Err(NNTPError::ResponseCode { received, .. }) => {
+                    log::debug!("CAPABILITIES failed with {}, assuming RFC 977", received);
+                    self.capabilities_unsupported = true;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(self.capabilities.as_ref())
+    }
+
+    // Leaves the connection unusable until re_connect, along with anything
+    // read from it but not consumed.
+    fn close_stream(&mut self) {
+        *self.stream.get_mut() = InnerStream::Closed;
+        self.stream.discard_buffer();
+    }
+
+    fn discard_capabilities(&mut self) {
+        self.capabilities = None;
+        self.capabilities_unsupported = false;
+    }
+
+    // Refuses to authenticate over a compressed connection (RFC 8054 §2.2.2),
+    // where compressed credentials could leak through the compressed size.
+    fn check_not_compressed(&self) -> Result<()> {
+        if self.is_compressed() {
+            return Err(NNTPError::CompressionError {
+                message: "authentication is not allowed after COMPRESS".to_owned(),
+            });
+        }
+        Ok(())
+    }
+
+    // Refuses to go on when the address requires STARTTLS but the connection
+    // is still in plain text, so credentials are never sent unencrypted.
+    fn check_starttls_done(&self) -> Result<()> {
+        if self.server_addr.requires_starttls() && self.stream.get_ref().tls_connection().is_none()
+        {
+            return Err(NNTPError::TlsError {
+                message: "STARTTLS is required before authentication".to_owned(),
+            });
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_valid_message(&self, message: &str) -> bool {
         //Carriage return
         let cr = 0x0d;
         //Line Feed
@@ -902,44 +2568,21 @@ impl NNTPStream {
                 && message_bytes[length - 5] == cr)
     }
 
+    pub(crate) fn write_command(&mut self, command: &[u8]) -> Result<()> {
+        match self.stream.write_all(command) {
+            Ok(_) => Ok(()),
+            Err(error) => Err(errors::write_error_or_network(error)),
+        }
+    }
+
     // Retrieve single line response
     // response matching any of the expected_code will be considered valid
-    fn read_response(
+    pub(crate) fn read_response(
         &mut self,
         expected_code: Vec<codes::ResponseCode>,
     ) -> Result<(isize, String)> {
-        //Carriage return
-        let cr = 0x0d;
-        //Line Feed
-        let lf = 0x0a;
-        let mut line_buffer: Vec<u8> = Vec::new();
-
-        while line_buffer.len() < 2
-            || (line_buffer[line_buffer.len() - 1] != lf
-                && line_buffer[line_buffer.len() - 2] != cr)
-        {
-            let byte_buffer: &mut [u8] = &mut [0];
-            match self.stream.read(byte_buffer) {
-                Ok(_) => {}
-                Err(error) => return Err(errors::response_error_or_network(error)),
-            }
-            line_buffer.push(byte_buffer[0]);
-        }
-
-        // Try to detect encoding and convert to UTF-8
-        // First try UTF-8, then fall back to WINDOWS-1252 (common in Usenet)
-        let (mut decoded_text, _, mut had_errors) = encoding_rs::UTF_8.decode(&line_buffer);
-
-        if had_errors {
-            // UTF-8 failed, try WINDOWS-1252
-            (decoded_text, _, had_errors) = encoding_rs::WINDOWS_1252.decode(&line_buffer);
-
-            if had_errors {
-                // error again ?
-                return Err(NNTPError::DecodingError);
-            }
-        }
-        let response = decoded_text.to_string();
+        self.read_line()?;
+        let response = decode_line(&self.line_buffer)?;
         let chars_to_trim: &[char] = &['\r', '\n'];
         let trimmed_response = response.trim_matches(chars_to_trim);
         let trimmed_response_vec: Vec<char> = trimmed_response.chars().collect();
@@ -978,47 +2621,168 @@ impl NNTPStream {
 
     fn read_multiline_response(&mut self) -> Result<Vec<String>> {
         let mut response: Vec<String> = Vec::new();
-        //Carriage return
-        let cr = 0x0d;
-        //Line Feed
-        let lf = 0x0a;
-        let mut line_buffer: Vec<u8> = Vec::new();
-        let mut complete = false;
+        let mut decoding_error = None;
 
-        while !complete {
-            while line_buffer.len() < 2
-                || (line_buffer[line_buffer.len() - 1] != lf
-                    && line_buffer[line_buffer.len() - 2] != cr)
-            {
-                let byte_buffer: &mut [u8] = &mut [0];
-                match self.stream.read(byte_buffer) {
-                    Ok(_) => {}
-                    Err(error) => return Err(errors::response_error_or_network(error)),
-                }
-                line_buffer.push(byte_buffer[0]);
+        while self.read_data_line()? {
+            // keep reading up to the terminator so the next response is intact
+            match decode_line(&self.line_buffer) {
+                Ok(line) => response.push(line),
+                Err(e) => decoding_error = decoding_error.or(Some(e)),
             }
+        }
+
+        match decoding_error {
+            Some(e) => Err(e),
+            None => Ok(response),
+        }
+    }
 
-            // Try to detect encoding and convert to UTF-8
-            // First try UTF-8, then fall back to WINDOWS-1252 (common in Usenet)
-            let (mut decoded_text, _, mut had_errors) = encoding_rs::UTF_8.decode(&line_buffer);
+    // Reads a multi-line block as bytes, lines concatenated, without the final `.` line
+    fn read_multiline_bytes(&mut self) -> Result<Vec<u8>> {
+        let mut response: Vec<u8> = Vec::new();
+        while self.read_data_line()? {
+            response.extend_from_slice(&self.line_buffer);
+        }
+        Ok(response)
+    }
 
-            if had_errors {
-                // UTF-8 failed, try WINDOWS-1252
-                (decoded_text, _, had_errors) = encoding_rs::WINDOWS_1252.decode(&line_buffer);
+    // Reads and decodes the next line of a multi-line block, or returns
+    // `None` once the terminating `.` line is reached.
+    pub(crate) fn next_data_line(&mut self) -> Result<Option<String>> {
+        if !self.read_data_line()? {
+            return Ok(None);
+        }
+        decode_line(&self.line_buffer).map(Some)
+    }
 
-                if had_errors {
-                    // error again ?
-                    return Err(NNTPError::DecodingError);
-                }
-            }
-            let decoded_text = decoded_text.to_string();
-            if decoded_text == ".\r\n" {
-                complete = true;
-            } else {
-                response.push(decoded_text);
-                line_buffer = Vec::new();
-            }
+    // Reads the lines of a multi-line block as bytes, without the final `.` line
+    fn read_multiline_raw(&mut self) -> Result<Vec<Vec<u8>>> {
+        let mut response: Vec<Vec<u8>> = Vec::new();
+        while self.read_data_line()? {
+            response.push(self.line_buffer.clone());
         }
         Ok(response)
     }
+
+    // Reads the next line of a multi-line block into line_buffer, returning
+    // false once the terminating `.` line is reached. Unless in raw mode,
+    // the line is dot-unstuffed.
+    fn read_data_line(&mut self) -> Result<bool> {
+        self.read_line()?;
+        if self.line_buffer == b".\r\n" {
+            return Ok(false);
+        }
+        if !self.raw_mode && self.line_buffer.starts_with(b"..") {
+            self.line_buffer.remove(0);
+        }
+        Ok(true)
+    }
+
+    // Reads a line, including its line ending, into line_buffer.
+    fn read_line(&mut self) -> Result<()> {
+        self.line_buffer.clear();
+        match self.stream.read_until(b'\n', &mut self.line_buffer) {
+            Ok(0) => Err(errors::response_error_or_network(std::io::Error::from(
+                std::io::ErrorKind::UnexpectedEof,
+            ))),
+            Ok(_) => Ok(()),
+            Err(error) => Err(errors::response_error_or_network(error)),
+        }
+    }
+}
+
+/// Decodes a line as UTF-8, falling back to WINDOWS-1252 (common in Usenet).
+fn decode_line(line: &[u8]) -> Result<String> {
+    let (mut decoded_text, _, mut had_errors) = encoding_rs::UTF_8.decode(line);
+
+    if had_errors {
+        // UTF-8 failed, try WINDOWS-1252
+        (decoded_text, _, had_errors) = encoding_rs::WINDOWS_1252.decode(line);
+
+        if had_errors {
+            // error again ?
+            return Err(NNTPError::DecodingError);
+        }
+    }
+    Ok(decoded_text.to_string())
+}
+
+/// Splits inflated multi-line data into lines, stopping at a `.` line if the
+/// compressed data includes one.
+fn decode_lines(data: &[u8], unstuff: bool) -> Result<Vec<String>> {
+    data.split_inclusive(|&byte| byte == b'\n')
+        .take_while(|&line| line != b".\r\n")
+        .map(|line| {
+            decode_line(if unstuff {
+                lines::unstuff_line(line)
+            } else {
+                line
+            })
+        })
+        .collect()
+}
+
+/// Parses the rows of a `LIST` response, skipping rows that cannot be parsed
+/// rather than failing the whole listing.
+fn parse_list_rows<T>(lines: &[String], parse: impl Fn(&str) -> Result<T>) -> Vec<T> {
+    lines
+        .iter()
+        .filter_map(|line| match parse(line) {
+            Ok(row) => Some(row),
+            Err(e) => {
+                log::warn!("Skipping LIST row: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Maps a 423 response to [`NNTPError::ArticleUnavailable`].
+fn article_unavailable(error: NNTPError) -> NNTPError {
+    match error {
+        NNTPError::ResponseCode { received: 423, .. } => NNTPError::ArticleUnavailable,
+        e => e,
+    }
+}
+
+fn compression_error(error: std::io::Error) -> NNTPError {
+    NNTPError::CompressionError {
+        message: error.to_string(),
+    }
+}
+
+/// Parses an `HDR`/`XHDR` response line of the form `number value`.
+fn parse_hdr_line(line: &str) -> Result<(isize, String)> {
+    let chars_to_trim: &[char] = &['\r', '\n'];
+    let trimmed_line = line.trim_end_matches(chars_to_trim);
+    let (number, value) = trimmed_line.split_once(' ').unwrap_or((trimmed_line, ""));
+
+    match number.parse::<isize>() {
+        Ok(number) => Ok((number, value.to_string())),
+        Err(_) => Err(NNTPError::InvalidResponse {
+            response: line.to_string(),
+        }),
+    }
+}
+
+/// Parses an `XHDR` response line. RFC 2980 servers start the line with the
+/// message ID when one was requested, which is returned as article number
+/// `0`, as `HDR` does.
+fn parse_xhdr_line(line: &str, by_message_id: bool) -> Result<(isize, String)> {
+    if by_message_id && line.starts_with('<') {
+        let chars_to_trim: &[char] = &['\r', '\n'];
+        let trimmed_line = line.trim_end_matches(chars_to_trim);
+        let (_, value) = trimmed_line.split_once(' ').unwrap_or((trimmed_line, ""));
+        return Ok((0, value.to_string()));
+    }
+    parse_hdr_line(line)
+}
+
+/// Encodes a SASL response; an empty response is sent as `=` (RFC 4643 §2.4.1).
+fn encode_sasl_response(response: &[u8]) -> String {
+    if response.is_empty() {
+        "=".to_owned()
+    } else {
+        sasl::base64_encode(response)
+    }
 }
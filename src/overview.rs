@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::errors::{NNTPError, Result};
+
+/// A single line of overview data, as returned by `OVER` or `XOVER`.
+///
+/// The overview database holds a summary of each article in a newsgroup,
+/// making it possible to list a group without fetching every article's headers.
+/// See [RFC 3977 §8.3](https://tools.ietf.org/html/rfc3977#section-8.3).
+///
+/// # Example
+///
+/// ```
+/// use nntp::OverviewEntry;
+///
+/// let line = "3000\tHello\tuser@example.com\t6 Oct 1998 04:38:40 -0500\t\
+///             <45223423@example.com>\t<45454@example.net>\t1234\t17\t\
+///             Xref: news.example.com misc.test:3000\r\n";
+/// let entry = OverviewEntry::parse(line).unwrap();
+/// assert_eq!(entry.number, 3000);
+/// assert_eq!(entry.subject, "Hello");
+/// assert_eq!(entry.message_id, "<45223423@example.com>");
+/// assert_eq!(entry.bytes, Some(1234));
+/// assert_eq!(entry.lines, Some(17));
+/// assert_eq!(
+///     entry.extra.get("Xref"),
+///     Some(&"news.example.com misc.test:3000".to_string())
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverviewEntry {
+    /// The article number, or `0` when the entry was requested by message ID.
+    pub number: isize,
+    /// The `Subject` header.
+    pub subject: String,
+    /// The `From` header.
+    pub from: String,
+    /// The `Date` header.
+    pub date: String,
+    /// The `Message-ID` header.
+    pub message_id: String,
+    /// The `References` header.
+    pub references: String,
+    /// The size of the article in octets, if reported by the server.
+    pub bytes: Option<usize>,
+    /// The number of lines in the article body, if reported by the server.
+    pub lines: Option<usize>,
+    /// Any other fields, keyed by header name (e.g. `"Xref"`).
+    ///
+    /// Names come from the server's `LIST OVERVIEW.FMT` when available. Fields
+    /// the format does not describe are keyed by their `Name:` prefix, if any.
+    pub extra: HashMap<String, String>,
+}
+
+/// A single field of the overview format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverviewField {
+    /// The header name (e.g. `"Subject"`) or metadata item (e.g. `":bytes"`),
+    /// without the trailing colon.
+    pub name: String,
+    /// Whether the server includes the header name in the field value
+    /// (`Xref:full`).
+    pub full: bool,
+}
+
+/// The order and content of overview fields, as returned by `LIST OVERVIEW.FMT`.
+///
+/// The first field of an overview line is always the article number and is not
+/// part of the format. See [RFC 3977 §8.4](https://tools.ietf.org/html/rfc3977#section-8.4).
+///
+/// # Example
+///
+/// ```
+/// use nntp::overview::OverviewFormat;
+///
+/// let lines = vec![
+///     "Subject:\r\n".to_string(),
+///     "From:\r\n".to_string(),
+///     "Date:\r\n".to_string(),
+///     "Message-ID:\r\n".to_string(),
+///     "References:\r\n".to_string(),
+///     ":bytes\r\n".to_string(),
+///     ":lines\r\n".to_string(),
+///     "Xref:full\r\n".to_string(),
+/// ];
+/// let format = OverviewFormat::from_list_response(&lines);
+/// assert_eq!(format.fields().len(), 8);
+/// assert_eq!(format.fields()[7].name, "Xref");
+/// assert!(format.fields()[7].full);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverviewFormat {
+    fields: Vec<OverviewField>,
+}
+
+impl Default for OverviewFormat {
+    /// The seven mandatory fields defined by RFC 3977.
+    fn default() -> Self {
+        let fields = [
+            "Subject",
+            "From",
+            "Date",
+            "Message-ID",
+            "References",
+            ":bytes",
+            ":lines",
+        ]
+        .iter()
+        .map(|name| OverviewField {
+            name: name.to_string(),
+            full: false,
+        })
+        .collect();
+        OverviewFormat { fields }
+    }
+}
+
+impl OverviewFormat {
+    /// Parses the lines returned by `LIST OVERVIEW.FMT`.
+    ///
+    /// The legacy `Bytes:` and `Lines:` spellings used by older servers are
+    /// mapped to the `:bytes` and `:lines` metadata items.
+    pub fn from_list_response(lines: &[String]) -> OverviewFormat {
+        let chars_to_trim: &[char] = &['\r', '\n', ' ', '\t'];
+        let fields = lines
+            .iter()
+            .map(|line| line.trim_matches(chars_to_trim))
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (name, full) = if line.to_ascii_lowercase().ends_with(":full") {
+                    (&line[..line.len() - 5], true)
+                } else {
+                    (line.trim_end_matches(':'), false)
+                };
+                let name = if name.eq_ignore_ascii_case("bytes") {
+                    ":bytes"
+                } else if name.eq_ignore_ascii_case("lines") {
+                    ":lines"
+                } else {
+                    name
+                };
+                OverviewField {
+                    name: name.to_string(),
+                    full,
+                }
+            })
+            .collect();
+        OverviewFormat { fields }
+    }
+
+    /// Returns the fields in the order the server sends them.
+    pub fn fields(&self) -> &[OverviewField] {
+        &self.fields
+    }
+}
+
+impl OverviewEntry {
+    /// Parses a single tab-separated overview line using the default
+    /// RFC 3977 field order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::InvalidResponse`] if the article number cannot be
+    /// parsed.
+    pub fn parse(line: &str) -> Result<OverviewEntry> {
+        Self::parse_with_format(line, &OverviewFormat::default())
+    }
+
+    /// Parses a single tab-separated overview line using the given format.
+    ///
+    /// Missing trailing fields are treated as empty, since some servers omit
+    /// them instead of sending empty values.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::InvalidResponse`] if the article number cannot be
+    /// parsed.
+    pub fn parse_with_format(line: &str, format: &OverviewFormat) -> Result<OverviewEntry> {
+        let chars_to_trim: &[char] = &['\r', '\n'];
+        let mut values = line.trim_end_matches(chars_to_trim).split('\t');
+
+        let number = values
+            .next()
+            .and_then(|n| isize::from_str(n.trim()).ok())
+            .ok_or_else(|| NNTPError::InvalidResponse {
+                response: line.to_string(),
+            })?;
+
+        let mut entry = OverviewEntry {
+            number,
+            subject: String::new(),
+            from: String::new(),
+            date: String::new(),
+            message_id: String::new(),
+            references: String::new(),
+            bytes: None,
+            lines: None,
+            extra: HashMap::new(),
+        };
+
+        for field in format.fields() {
+            let value = values.next().unwrap_or_default();
+            let value = if field.full {
+                strip_header_name(value).map_or(value, |(_, v)| v)
+            } else {
+                value
+            };
+
+            match field.name.to_ascii_lowercase().as_str() {
+                "subject" => entry.subject = value.to_string(),
+                "from" => entry.from = value.to_string(),
+                "date" => entry.date = value.to_string(),
+                "message-id" => entry.message_id = value.to_string(),
+                "references" => entry.references = value.to_string(),
+                ":bytes" => entry.bytes = value.trim().parse().ok(),
+                ":lines" => entry.lines = value.trim().parse().ok(),
+                _ => {
+                    entry.extra.insert(field.name.clone(), value.to_string());
+                }
+            }
+        }
+
+        // fields beyond the advertised format are usually "Name: value" headers
+        for value in values {
+            if let Some((name, value)) = strip_header_name(value) {
+                entry.extra.insert(name.to_string(), value.to_string());
+            }
+        }
+
+        Ok(entry)
+    }
+}
+
+/// Splits a `Name: value` overview field into its name and value.
+fn strip_header_name(value: &str) -> Option<(&str, &str)> {
+    let (name, rest) = value.split_once(':')?;
+    if name.is_empty() || name.contains(char::is_whitespace) {
+        return None;
+    }
+    Some((name, rest.trim_start()))
+}
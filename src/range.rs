@@ -0,0 +1,57 @@
+use std::fmt;
+use std::ops::{RangeFrom, RangeInclusive};
+
+/// A range of article numbers, as accepted by commands such as `OVER`.
+///
+/// Ranges follow the RFC 3977 §6.1.1 syntax:
+/// - `n` — a single article
+/// - `n-` — all articles from `n` onwards
+/// - `n-m` — articles `n` through `m` inclusive
+///
+/// # Example
+///
+/// ```
+/// use nntp::ArticleRange;
+///
+/// assert_eq!(ArticleRange::from(42).to_string(), "42");
+/// assert_eq!(ArticleRange::from(100..).to_string(), "100-");
+/// assert_eq!(ArticleRange::from(100..=200).to_string(), "100-200");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArticleRange {
+    /// A single article number.
+    Number(isize),
+    /// All articles starting at the given number.
+    From(isize),
+    /// Articles between the two numbers, inclusive.
+    Between(isize, isize),
+}
+
+impl fmt::Display for ArticleRange {
+    /// Formats the range using the RFC 3977 wire syntax.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArticleRange::Number(n) => write!(f, "{}", n),
+            ArticleRange::From(low) => write!(f, "{}-", low),
+            ArticleRange::Between(low, high) => write!(f, "{}-{}", low, high),
+        }
+    }
+}
+
+impl From<isize> for ArticleRange {
+    fn from(number: isize) -> Self {
+        ArticleRange::Number(number)
+    }
+}
+
+impl From<RangeFrom<isize>> for ArticleRange {
+    fn from(range: RangeFrom<isize>) -> Self {
+        ArticleRange::From(range.start)
+    }
+}
+
+impl From<RangeInclusive<isize>> for ArticleRange {
+    fn from(range: RangeInclusive<isize>) -> Self {
+        ArticleRange::Between(*range.start(), *range.end())
+    }
+}
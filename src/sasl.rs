@@ -0,0 +1,557 @@
+//! SASL mechanisms for `AUTHINFO SASL` (RFC 4643 §2.4).
+//!
+//! This module defines the [`SaslMechanism`] trait used by
+//! [`NNTPStream::authenticate_sasl`](crate::NNTPStream::authenticate_sasl),
+//! along with the built-in mechanisms:
+//!
+//! - [`Plain`] — `PLAIN` (RFC 4616), sends the password in clear text and
+//!   should only be used over TLS
+//! - [`CramMd5`] — `CRAM-MD5` (RFC 2195), a challenge-response mechanism
+//! - [`ScramSha256`] — `SCRAM-SHA-256` (RFC 7677), with `SCRAM-SHA-256-PLUS`
+//!   channel binding when the connection uses TLS 1.3 and the server offers it
+//! - [`External`] — `EXTERNAL` (RFC 4422), relying on the TLS client
+//!   certificate configured in [`TlsConfig::client_cert`](crate::TlsConfig::client_cert)
+//!
+//! # Example
+//!
+//! ```no_run
+//! use nntp::NNTPStream;
+//! use nntp::sasl::ScramSha256;
+//!
+//! let mut client = NNTPStream::connect("nntps://nntp.example.com".to_string())
+//!     .expect("Failed to connect");
+//! client.authenticate_sasl(ScramSha256::new("user", "password"))
+//!     .expect("Authentication failed");
+//! ```
+
+use std::fmt;
+
+use hmac::{Hmac, KeyInit, Mac};
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+use crate::errors::{NNTPError, Result};
+
+/// The highest SCRAM iteration count accepted from a server, so that a
+/// hostile server cannot keep the client busy hashing. Well above the counts
+/// recommended for PBKDF2-HMAC-SHA-256 today.
+const MAX_SCRAM_ITERATIONS: u32 = 1_000_000;
+
+/// Channel binding data taken from the TLS connection (RFC 5056).
+///
+/// Mechanisms that support channel binding use it to prove that the
+/// authentication happened over the same TLS connection, defeating
+/// man-in-the-middle attacks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelBinding {
+    /// The channel binding type, e.g. `"tls-exporter"` (RFC 9266).
+    pub kind: &'static str,
+    /// The channel binding data.
+    pub data: Vec<u8>,
+    /// The SASL mechanisms the server advertises in its capabilities, so that
+    /// a mechanism only uses its channel-bound variant when it is offered.
+    pub server_mechanisms: Vec<String>,
+}
+
+impl ChannelBinding {
+    /// Checks whether the server advertises the given mechanism.
+    pub fn is_advertised(&self, mechanism: &str) -> bool {
+        self.server_mechanisms
+            .iter()
+            .any(|m| m.eq_ignore_ascii_case(mechanism))
+    }
+}
+
+/// A SASL authentication mechanism.
+///
+/// The exchange is driven by [`NNTPStream::authenticate_sasl`](crate::NNTPStream::authenticate_sasl):
+///
+/// 1. [`SaslMechanism::start`] begins a new exchange and may return an
+///    initial response, sent along with `AUTHINFO SASL`.
+/// 2. [`SaslMechanism::name`] gives the mechanism name to send.
+/// 3. [`SaslMechanism::step`] is called for each server challenge (383).
+/// 4. [`SaslMechanism::finish`] is called once the server accepts the
+///    authentication (281 or 283), with the success data if any.
+///
+/// `start` must reset any state left from a previous exchange, since the same
+/// mechanism is reused to re-authenticate after a reconnect.
+pub trait SaslMechanism: fmt::Debug + Send {
+    /// Returns the mechanism name sent to the server, e.g. `"PLAIN"`.
+    ///
+    /// This is called after [`SaslMechanism::start`], so it may depend on
+    /// whether channel binding is in use.
+    fn name(&self) -> &str;
+
+    /// Begins a new exchange and returns the initial response, if the
+    /// mechanism sends one.
+    ///
+    /// `channel_binding` is set when the connection uses TLS 1.3. Mechanisms
+    /// should only bind to the channel when the server advertises their
+    /// channel-bound variant, see [`ChannelBinding::is_advertised`].
+    fn start(&mut self, channel_binding: Option<&ChannelBinding>) -> Result<Option<Vec<u8>>>;
+
+    /// Computes the response to a server challenge.
+    fn step(&mut self, challenge: &[u8]) -> Result<Vec<u8>>;
+
+    /// Checks the outcome of a successful exchange.
+    ///
+    /// `data` holds the additional success data sent with code 283.
+    fn finish(&mut self, data: Option<&[u8]>) -> Result<()> {
+        match data {
+            Some(_) => Err(sasl_error("unexpected success data")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The `PLAIN` mechanism (RFC 4616).
+///
+/// Sends the username and password in clear text; only use it over TLS.
+#[derive(Debug, Clone)]
+pub struct Plain {
+    authzid: String,
+    username: String,
+    password: String,
+}
+
+impl Plain {
+    /// Creates a `PLAIN` mechanism for the given credentials.
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Plain {
+            authzid: String::new(),
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+
+    /// Sets the authorization identity to act as, if different from the
+    /// username.
+    pub fn with_authzid(mut self, authzid: impl Into<String>) -> Self {
+        self.authzid = authzid.into();
+        self
+    }
+}
+
+impl SaslMechanism for Plain {
+    fn name(&self) -> &str {
+        "PLAIN"
+    }
+
+    fn start(&mut self, _channel_binding: Option<&ChannelBinding>) -> Result<Option<Vec<u8>>> {
+        let message = format!("{}\0{}\0{}", self.authzid, self.username, self.password);
+        Ok(Some(message.into_bytes()))
+    }
+
+    fn step(&mut self, _challenge: &[u8]) -> Result<Vec<u8>> {
+        Err(sasl_error("PLAIN does not expect a challenge"))
+    }
+}
+
+/// The `EXTERNAL` mechanism (RFC 4422 Appendix A).
+///
+/// The server authenticates the client from credentials established outside
+/// of SASL, typically the TLS client certificate. See
+/// [`NNTPStream::authenticate_sasl_external`](crate::NNTPStream::authenticate_sasl_external).
+#[derive(Debug, Clone, Default)]
+pub struct External {
+    authzid: String,
+}
+
+impl External {
+    /// Creates an `EXTERNAL` mechanism that acts as the identity derived from
+    /// the client certificate.
+    pub fn new() -> Self {
+        External::default()
+    }
+
+    /// Sets the authorization identity to act as, if different from the one
+    /// derived from the client certificate.
+    pub fn with_authzid(mut self, authzid: impl Into<String>) -> Self {
+        self.authzid = authzid.into();
+        self
+    }
+}
+
+impl SaslMechanism for External {
+    fn name(&self) -> &str {
+        "EXTERNAL"
+    }
+
+    fn start(&mut self, _channel_binding: Option<&ChannelBinding>) -> Result<Option<Vec<u8>>> {
+        Ok(Some(self.authzid.clone().into_bytes()))
+    }
+
+    fn step(&mut self, _challenge: &[u8]) -> Result<Vec<u8>> {
+        Err(sasl_error("EXTERNAL does not expect a challenge"))
+    }
+}
+
+/// The `CRAM-MD5` mechanism (RFC 2195).
+///
+/// # Example
+///
+/// ```
+/// use nntp::sasl::{CramMd5, SaslMechanism};
+///
+/// // RFC 2195 §2 example exchange
+/// let mut mechanism = CramMd5::new("tim", "tanstaaftanstaaf");
+/// assert_eq!(mechanism.start(None).unwrap(), None);
+/// let response = mechanism
+///     .step(b"<1896.697170952@postoffice.reston.mci.net>")
+///     .unwrap();
+/// assert_eq!(response, b"tim b913a602c7eda7a495b4e6e7334d3890");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CramMd5 {
+    username: String,
+    password: String,
+}
+
+impl CramMd5 {
+    /// Creates a `CRAM-MD5` mechanism for the given credentials.
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        CramMd5 {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+}
+
+impl SaslMechanism for CramMd5 {
+    fn name(&self) -> &str {
+        "CRAM-MD5"
+    }
+
+    fn start(&mut self, _channel_binding: Option<&ChannelBinding>) -> Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    fn step(&mut self, challenge: &[u8]) -> Result<Vec<u8>> {
+        let mut mac = Hmac::<Md5>::new_from_slice(self.password.as_bytes())
+            .map_err(|e| sasl_error(&e.to_string()))?;
+        mac.update(challenge);
+        let digest = to_hex(&mac.finalize().into_bytes());
+        Ok(format!("{} {}", self.username, digest).into_bytes())
+    }
+}
+
+/// The state of a SCRAM exchange.
+#[derive(Debug, Clone)]
+enum ScramState {
+    Initial,
+    ClientFirstSent {
+        gs2_header: String,
+        client_first_bare: String,
+        client_nonce: String,
+        channel_binding: Option<Vec<u8>>,
+    },
+    ClientFinalSent {
+        server_signature: Vec<u8>,
+    },
+    Verified,
+}
+
+/// The `SCRAM-SHA-256` mechanism (RFC 7677), and its channel-bound
+/// `SCRAM-SHA-256-PLUS` variant.
+///
+/// When the connection uses TLS 1.3 and the server advertises
+/// `SCRAM-SHA-256-PLUS`, the `tls-exporter` channel binding is used and the
+/// mechanism is sent under that name, unless disabled with
+/// [`ScramSha256::without_channel_binding`]. Otherwise plain `SCRAM-SHA-256`
+/// is used. The server's signature is always
+/// verified, so a successful exchange also authenticates the server.
+#[derive(Debug, Clone)]
+pub struct ScramSha256 {
+    username: String,
+    password: String,
+    use_channel_binding: bool,
+    plus: bool,
+    state: ScramState,
+}
+
+impl ScramSha256 {
+    /// Creates a `SCRAM-SHA-256` mechanism for the given credentials.
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        ScramSha256 {
+            username: username.into(),
+            password: password.into(),
+            use_channel_binding: true,
+            plus: false,
+            state: ScramState::Initial,
+        }
+    }
+
+    /// Never uses channel binding, even when the server offers
+    /// `SCRAM-SHA-256-PLUS`.
+    ///
+    /// The client then tells the server it does not support channel binding
+    /// at all (gs2 flag `n`), which a server offering `SCRAM-SHA-256-PLUS`
+    /// accepts.
+    pub fn without_channel_binding(mut self) -> Self {
+        self.use_channel_binding = false;
+        self
+    }
+
+    fn start_with_nonce(
+        &mut self,
+        channel_binding: Option<&ChannelBinding>,
+        client_nonce: String,
+    ) -> Vec<u8> {
+        let (gs2_header, data) = match channel_binding {
+            Some(cb) if self.use_channel_binding && cb.is_advertised("SCRAM-SHA-256-PLUS") => {
+                (format!("p={},,", cb.kind), Some(cb.data.clone()))
+            }
+            // the client could bind, but thinks the server cannot (RFC 5802 §6)
+            Some(_) if self.use_channel_binding => ("y,,".to_owned(), None),
+            // no binding available, or the caller turned it off: a server
+            // offering -PLUS must not take this as a downgrade
+            _ => ("n,,".to_owned(), None),
+        };
+        self.plus = data.is_some();
+
+        let client_first_bare = format!("n={},r={}", scram_escape(&self.username), client_nonce);
+        let client_first = format!("{}{}", gs2_header, client_first_bare);
+        self.state = ScramState::ClientFirstSent {
+            gs2_header,
+            client_first_bare,
+            client_nonce,
+            channel_binding: data,
+        };
+        client_first.into_bytes()
+    }
+}
+
+impl SaslMechanism for ScramSha256 {
+    fn name(&self) -> &str {
+        if self.plus {
+            "SCRAM-SHA-256-PLUS"
+        } else {
+            "SCRAM-SHA-256"
+        }
+    }
+
+    fn start(&mut self, channel_binding: Option<&ChannelBinding>) -> Result<Option<Vec<u8>>> {
+        let mut nonce = [0u8; 24];
+        getrandom::fill(&mut nonce).map_err(|e| sasl_error(&e.to_string()))?;
+        let client_nonce = base64_encode(&nonce);
+        Ok(Some(self.start_with_nonce(channel_binding, client_nonce)))
+    }
+
+    fn step(&mut self, challenge: &[u8]) -> Result<Vec<u8>> {
+        match std::mem::replace(&mut self.state, ScramState::Initial) {
+            ScramState::ClientFirstSent {
+                gs2_header,
+                client_first_bare,
+                client_nonce,
+                channel_binding,
+            } => {
+                let server_first =
+                    std::str::from_utf8(challenge).map_err(|e| sasl_error(&e.to_string()))?;
+                let nonce = scram_attribute(server_first, 'r')?;
+                let salt = base64_decode(scram_attribute(server_first, 's')?)?;
+                let iterations: u32 = scram_attribute(server_first, 'i')?
+                    .parse()
+                    .map_err(|_| sasl_error("invalid iteration count"))?;
+                if iterations == 0 || iterations > MAX_SCRAM_ITERATIONS {
+                    return Err(sasl_error(&format!(
+                        "iteration count {} out of range",
+                        iterations
+                    )));
+                }
+                if !nonce.starts_with(&client_nonce) || nonce.len() == client_nonce.len() {
+                    return Err(sasl_error("server nonce does not extend client nonce"));
+                }
+
+                let mut binding = gs2_header.into_bytes();
+                binding.extend(channel_binding.unwrap_or_default());
+                let client_final_without_proof =
+                    format!("c={},r={}", base64_encode(&binding), nonce);
+                let auth_message = format!(
+                    "{},{},{}",
+                    client_first_bare, server_first, client_final_without_proof
+                );
+
+                let mut salted_password = [0u8; 32];
+                pbkdf2::pbkdf2_hmac::<Sha256>(
+                    self.password.as_bytes(),
+                    &salt,
+                    iterations,
+                    &mut salted_password,
+                );
+                let client_key = hmac_sha256(&salted_password, b"Client Key")?;
+                let stored_key = Sha256::digest(&client_key);
+                let client_signature = hmac_sha256(&stored_key, auth_message.as_bytes())?;
+                let proof: Vec<u8> = client_key
+                    .iter()
+                    .zip(client_signature.iter())
+                    .map(|(k, s)| k ^ s)
+                    .collect();
+                let server_key = hmac_sha256(&salted_password, b"Server Key")?;
+                let server_signature = hmac_sha256(&server_key, auth_message.as_bytes())?;
+
+                self.state = ScramState::ClientFinalSent { server_signature };
+                Ok(
+                    format!("{},p={}", client_final_without_proof, base64_encode(&proof))
+                        .into_bytes(),
+                )
+            }
+            // some servers send the server-final message as a challenge
+            ScramState::ClientFinalSent { server_signature } => {
+                self.verify_server_final(challenge, &server_signature)?;
+                Ok(Vec::new())
+            }
+            _ => Err(sasl_error("unexpected challenge")),
+        }
+    }
+
+    fn finish(&mut self, data: Option<&[u8]>) -> Result<()> {
+        match (
+            std::mem::replace(&mut self.state, ScramState::Initial),
+            data,
+        ) {
+            (ScramState::ClientFinalSent { server_signature }, Some(data)) => {
+                self.verify_server_final(data, &server_signature)
+            }
+            (ScramState::Verified, None) => Ok(()),
+            _ => Err(sasl_error("server signature was not received")),
+        }
+    }
+}
+
+impl ScramSha256 {
+    fn verify_server_final(&mut self, server_final: &[u8], expected: &[u8]) -> Result<()> {
+        let server_final =
+            std::str::from_utf8(server_final).map_err(|e| sasl_error(&e.to_string()))?;
+        if let Ok(error) = scram_attribute(server_final, 'e') {
+            return Err(sasl_error(&format!("server error: {}", error)));
+        }
+        let signature = base64_decode(scram_attribute(server_final, 'v')?)?;
+        if signature != expected {
+            return Err(sasl_error("server signature mismatch"));
+        }
+        self.state = ScramState::Verified;
+        Ok(())
+    }
+}
+
+fn sasl_error(message: &str) -> NNTPError {
+    NNTPError::SaslError {
+        message: message.to_owned(),
+    }
+}
+
+pub(crate) fn base64_encode(data: &[u8]) -> String {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD.encode(data)
+}
+
+pub(crate) fn base64_decode(data: &str) -> Result<Vec<u8>> {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD
+        .decode(data.trim())
+        .map_err(|e| sasl_error(&format!("invalid base64: {}", e)))
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).map_err(|e| sasl_error(&e.to_string()))?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+fn to_hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Escapes a SCRAM username (RFC 5802 §5.1).
+fn scram_escape(name: &str) -> String {
+    name.replace('=', "=3D").replace(',', "=2C")
+}
+
+/// Returns the value of a single-letter SCRAM attribute, e.g. `r=...`.
+fn scram_attribute(message: &str, name: char) -> Result<&str> {
+    message
+        .split(',')
+        .find_map(|attribute| {
+            let mut chars = attribute.chars();
+            match (chars.next(), chars.next()) {
+                (Some(n), Some('=')) if n == name => Some(&attribute[2..]),
+                _ => None,
+            }
+        })
+        .ok_or_else(|| sasl_error(&format!("missing SCRAM attribute '{}'", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 7677 §3
+    const CLIENT_NONCE: &str = "rOprNGfwEbeRWgbNEkqO";
+    const SERVER_FIRST: &str = "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+                                s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+    const CLIENT_FINAL: &str = "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+                                p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=";
+    const SERVER_FINAL: &str = "v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=";
+
+    fn tls_binding(server_mechanisms: &[&str]) -> ChannelBinding {
+        ChannelBinding {
+            kind: "tls-exporter",
+            data: vec![1, 2, 3],
+            server_mechanisms: server_mechanisms.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn scram_sha256_rfc7677_exchange() {
+        let mut scram = ScramSha256::new("user", "pencil");
+        let client_first = scram.start_with_nonce(None, CLIENT_NONCE.to_owned());
+        assert_eq!(client_first, b"n,,n=user,r=rOprNGfwEbeRWgbNEkqO");
+        assert_eq!(scram.name(), "SCRAM-SHA-256");
+
+        let client_final = scram.step(SERVER_FIRST.as_bytes()).unwrap();
+        assert_eq!(client_final, CLIENT_FINAL.as_bytes());
+
+        scram.finish(Some(SERVER_FINAL.as_bytes())).unwrap();
+    }
+
+    #[test]
+    fn scram_sha256_rejects_tampered_server_signature() {
+        let mut scram = ScramSha256::new("user", "pencil");
+        scram.start_with_nonce(None, CLIENT_NONCE.to_owned());
+        scram.step(SERVER_FIRST.as_bytes()).unwrap();
+
+        let tampered = "v=7rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=";
+        assert!(scram.finish(Some(tampered.as_bytes())).is_err());
+    }
+
+    #[test]
+    fn scram_sha256_rejects_excessive_iteration_count() {
+        let mut scram = ScramSha256::new("user", "pencil");
+        scram.start_with_nonce(None, CLIENT_NONCE.to_owned());
+        let server_first = SERVER_FIRST.replace("i=4096", "i=4294967295");
+        assert!(scram.step(server_first.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn scram_sha256_binds_only_when_plus_is_advertised() {
+        let mut scram = ScramSha256::new("user", "pencil");
+        let binding = tls_binding(&["SCRAM-SHA-256"]);
+        let client_first = scram.start_with_nonce(Some(&binding), CLIENT_NONCE.to_owned());
+        assert!(client_first.starts_with(b"y,,n=user,"));
+        assert_eq!(scram.name(), "SCRAM-SHA-256");
+
+        let binding = tls_binding(&["SCRAM-SHA-256", "SCRAM-SHA-256-PLUS"]);
+        let client_first = scram.start_with_nonce(Some(&binding), CLIENT_NONCE.to_owned());
+        assert!(client_first.starts_with(b"p=tls-exporter,,n=user,"));
+        assert_eq!(scram.name(), "SCRAM-SHA-256-PLUS");
+
+        let mut scram = scram.without_channel_binding();
+        let client_first = scram.start_with_nonce(Some(&binding), CLIENT_NONCE.to_owned());
+        assert!(client_first.starts_with(b"n,,n=user,"));
+        assert_eq!(scram.name(), "SCRAM-SHA-256");
+
+        let binding = tls_binding(&["SCRAM-SHA-256"]);
+        let client_first = scram.start_with_nonce(Some(&binding), CLIENT_NONCE.to_owned());
+        assert!(client_first.starts_with(b"n,,n=user,"));
+    }
+}
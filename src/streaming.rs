@@ -0,0 +1,252 @@
+//! Streaming article transfer (RFC 4644).
+//!
+//! A [`StreamingSession`] pipelines `CHECK` and `TAKETHIS` commands instead of
+//! waiting for each `IHAVE` exchange to complete, which keeps a transit feed
+//! busy even over high-latency links. Create one with
+//! [`NNTPStream::mode_stream`].
+
+use std::collections::VecDeque;
+use std::string::String;
+
+use crate::codes::ResponseCode;
+use crate::errors::{NNTPError, Result};
+use crate::lines;
+use crate::nntp_stream::NNTPStream;
+
+/// The default number of commands awaiting a response.
+const DEFAULT_WINDOW: usize = 64;
+
+/// The server's answer to a `CHECK` or `TAKETHIS` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamOutcome {
+    /// `CHECK`: the server wants the article, send it with `TAKETHIS` (238).
+    Wanted,
+    /// `CHECK`: the server cannot take the article now, try again later (431).
+    TryLater,
+    /// `CHECK`: the server does not want the article (438).
+    NotWanted,
+    /// `TAKETHIS`: the article was transferred (239).
+    Transferred,
+    /// `TAKETHIS`: the article was rejected; do not send it again (439).
+    Rejected,
+}
+
+/// The result of a single `CHECK` or `TAKETHIS` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamResponse {
+    /// The message ID the response is about.
+    pub message_id: String,
+    /// What the server answered.
+    pub outcome: StreamOutcome,
+}
+
+/// A streaming feed session on an [`NNTPStream`].
+///
+/// Commands are sent right away as long as fewer than the window's worth of
+/// responses are outstanding; otherwise the oldest response is read first and
+/// kept until it is asked for. Responses are returned by the session's
+/// [`Iterator`] implementation, which reads from the server until no command
+/// is outstanding.
+///
+/// Responses are matched to commands by the message ID they carry, so the
+/// server may answer out of order. A response about a message ID with no
+/// outstanding command is returned as [`NNTPError::InvalidResponse`].
+///
+/// Dropping the session reads any outstanding responses, so that the
+/// connection can be used for other commands afterwards.
+///
+/// # Example
+///
+/// ```no_run
+/// use nntp::NNTPStream;
+/// use nntp::streaming::StreamOutcome;
+///
+/// # fn load_article(_: &str) -> String { String::new() }
+/// let mut client = NNTPStream::connect("peer.example.com:119".to_string())
+///     .expect("Failed to connect");
+/// let mut session = client.mode_stream().expect("Streaming refused").with_window(100);
+///
+/// for message_id in ["<a@example.com>", "<b@example.com>"] {
+///     session.check(message_id).expect("Failed to send CHECK");
+/// }
+/// while let Some(response) = session.next() {
+///     let response = response.expect("Feed failed");
+///     if response.outcome == StreamOutcome::Wanted {
+///         let article = load_article(&response.message_id);
+///         session.takethis(&response.message_id, &article)
+///             .expect("Failed to send TAKETHIS");
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct StreamingSession<'a> {
+    stream: &'a mut NNTPStream,
+    window: usize,
+    in_flight: VecDeque<String>,
+    completed: VecDeque<StreamResponse>,
+}
+
+impl<'a> StreamingSession<'a> {
+    pub(crate) fn new(stream: &'a mut NNTPStream) -> Self {
+        StreamingSession {
+            stream,
+            window: DEFAULT_WINDOW,
+            in_flight: VecDeque::new(),
+            completed: VecDeque::new(),
+        }
+    }
+
+    /// Sets how many commands may await a response at once (64 by default).
+    ///
+    /// A window of 1 makes the session lock-step, like `IHAVE`.
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    /// Asks the server whether it wants the article, with `CHECK`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the command cannot be sent, or if reading a
+    /// response to make room in the window fails.
+    pub fn check(&mut self, message_id: &str) -> Result<()> {
+        self.make_room()?;
+        self.stream
+            .write_command(format!("CHECK {}\r\n", message_id).as_bytes())?;
+        self.in_flight.push_back(message_id.to_owned());
+        Ok(())
+    }
+
+    /// Sends the article with `TAKETHIS`, without waiting for the server to
+    /// accept it.
+    ///
+    /// The `article` must be complete, dot-stuffed and terminated with
+    /// `\r\n.\r\n`; [`StreamingSession::takethis_article`] takes plain
+    /// content instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::InvalidMessage`] if the article does not end with
+    /// the required terminator, or an error if it cannot be sent.
+    pub fn takethis(&mut self, message_id: &str, article: &str) -> Result<()> {
+        if !self.stream.is_valid_message(article) {
+            return Err(NNTPError::InvalidMessage {
+                message: article.to_owned(),
+                reason: "Invalid message format. Message must end with \"\r\n.\r\n\"".to_owned(),
+            });
+        }
+
+        self.make_room()?;
+        self.stream
+            .write_command(format!("TAKETHIS {}\r\n", message_id).as_bytes())?;
+        self.stream.write_command(article.as_bytes())?;
+        self.in_flight.push_back(message_id.to_owned());
+        Ok(())
+    }
+
+    /// Sends an article given as plain content, without the terminating `.`
+    /// line, with `TAKETHIS`. The content is encoded as in
+    /// [`NNTPStream::post_article`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the article cannot be sent.
+    pub fn takethis_article(&mut self, message_id: &str, content: &str) -> Result<()> {
+        self.takethis(message_id, &lines::encode_data_block(content))
+    }
+
+    /// Returns the number of commands still awaiting a response.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Reads responses until every command has been answered and returns
+    /// the ones not yet taken from the iterator.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error met while reading responses.
+    pub fn finish(mut self) -> Result<Vec<StreamResponse>> {
+        while !self.in_flight.is_empty() {
+            let response = self.read_next_response()?;
+            self.completed.push_back(response);
+        }
+        Ok(self.completed.drain(..).collect())
+    }
+
+    fn make_room(&mut self) -> Result<()> {
+        while self.in_flight.len() >= self.window {
+            let response = self.read_next_response()?;
+            self.completed.push_back(response);
+        }
+        Ok(())
+    }
+
+    fn read_next_response(&mut self) -> Result<StreamResponse> {
+        let result = self.stream.read_response(vec![
+            ResponseCode::CheckSendArticle,
+            ResponseCode::CheckTryLater,
+            ResponseCode::CheckNotWanted,
+            ResponseCode::TakethisTransferred,
+            ResponseCode::TakethisRejected,
+        ]);
+        let (code, message) = match result {
+            Ok(response) => response,
+            Err(e) => {
+                // the command is answered, even if not with a code we know
+                self.in_flight.pop_front();
+                return Err(e);
+            }
+        };
+
+        // responses carry the message ID, so use it rather than trusting order
+        let message_id = message.split_whitespace().next().unwrap_or_default();
+        let Some(position) = self.in_flight.iter().position(|id| id == message_id) else {
+            // a command was answered, but there is no telling which one
+            self.in_flight.pop_front();
+            return Err(NNTPError::InvalidResponse {
+                response: format!("{} {}", code, message),
+            });
+        };
+        let message_id = self.in_flight.remove(position).unwrap_or_default();
+
+        let outcome = match code {
+            code if code == ResponseCode::CheckSendArticle.into() => StreamOutcome::Wanted,
+            code if code == ResponseCode::CheckTryLater.into() => StreamOutcome::TryLater,
+            code if code == ResponseCode::CheckNotWanted.into() => StreamOutcome::NotWanted,
+            code if code == ResponseCode::TakethisTransferred.into() => StreamOutcome::Transferred,
+            _ => StreamOutcome::Rejected,
+        };
+        Ok(StreamResponse {
+            message_id,
+            outcome,
+        })
+    }
+}
+
+impl Iterator for StreamingSession<'_> {
+    type Item = Result<StreamResponse>;
+
+    /// Returns the next response, reading from the server if none is
+    /// buffered, or `None` once no command is outstanding.
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(response) = self.completed.pop_front() {
+            return Some(Ok(response));
+        }
+        if self.in_flight.is_empty() {
+            return None;
+        }
+        Some(self.read_next_response())
+    }
+}
+
+impl Drop for StreamingSession<'_> {
+    fn drop(&mut self) {
+        while !self.in_flight.is_empty() {
+            if let Err(e) = self.read_next_response() {
+                log::warn!("Discarding streaming response failed: {}", e);
+            }
+        }
+    }
+}
@@ -27,26 +27,43 @@
 //! let addr = ServerAddress::with_tls(
 //!     "nntp.example.com",
 //!     563,
-//!     TlsConfig { danger_accept_invalid_certs: true },
+//!     TlsConfig { danger_accept_invalid_certs: true, ..Default::default() },
 //! );
 //! let client = NNTPStream::connect_with(addr);
+//!
+//! // Plain connection on port 119, upgraded with STARTTLS before authenticating
+//! let addr = ServerAddress::with_starttls("nntp.example.com", 119, TlsConfig::default());
+//! let client = NNTPStream::connect_with(addr);
 //! ```
 
-use crate::address::ServerAddress;
+use std::fmt;
 use std::net::TcpStream;
+use std::path::Path;
 use std::sync::Arc;
 
-use rustls::pki_types::ServerName;
-use rustls::{ClientConfig, ClientConnection, StreamOwned};
-use rustls_platform_verifier::ConfigVerifierExt;
+use rustls::client::WantsClientCert;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::{ClientConfig, ClientConnection, ConfigBuilder, StreamOwned};
+use rustls_platform_verifier::BuilderVerifierExt;
+
+use crate::errors::{self, NNTPError};
 
 /// Builds a rustls `ClientConfig` using the platform's native certificate verifier.
-pub(crate) fn build_client_config(tls_config: &TlsConfig) -> Result<ClientConfig, std::io::Error> {
-    if tls_config.danger_accept_invalid_certs {
+pub(crate) fn build_client_config(tls_config: &TlsConfig) -> std::io::Result<ClientConfig> {
+    let builder = if tls_config.danger_accept_invalid_certs {
         build_dangerous_config()
     } else {
-        ClientConfig::with_platform_verifier()
-            .map_err(|e| std::io::Error::other(format!("failed to build TLS config: {e}")))
+        ClientConfig::builder()
+            .with_platform_verifier()
+            .map_err(|e| std::io::Error::other(format!("failed to build TLS config: {e}")))?
+    };
+
+    match &tls_config.client_cert {
+        Some(client_cert) => builder
+            .with_client_auth_cert(client_cert.cert_chain.clone(), client_cert.key.clone_key())
+            .map_err(|e| std::io::Error::other(format!("invalid client certificate: {e}"))),
+        None => Ok(builder.with_no_client_auth()),
     }
 }
 
@@ -58,10 +75,120 @@ pub struct TlsConfig {
     /// **WARNING:** This makes the connection vulnerable to man-in-the-middle
     /// attacks. Only use for testing or when you understand the risks.
     pub danger_accept_invalid_certs: bool,
+    /// Connect in plain text and upgrade with `STARTTLS` (RFC 4642) instead
+    /// of starting the TLS handshake immediately.
+    ///
+    /// When set, authentication is refused until the upgrade has succeeded.
+    pub starttls: bool,
+    /// Client certificate presented during the handshake, for servers that
+    /// authenticate clients by certificate (see
+    /// [`NNTPStream::authenticate_sasl_external`](crate::NNTPStream::authenticate_sasl_external)).
+    pub client_cert: Option<ClientCertificate>,
+}
+
+/// A client certificate chain and its private key.
+///
+/// # Example
+///
+/// ```no_run
+/// use nntp::{NNTPStream, ServerAddress, TlsConfig};
+/// use nntp::tls::ClientCertificate;
+///
+/// let client_cert = ClientCertificate::from_pem_files("client.crt", "client.key")
+///     .expect("Failed to load client certificate");
+/// let addr = ServerAddress::with_tls(
+///     "nntp.example.com",
+///     563,
+///     TlsConfig { client_cert: Some(client_cert), ..Default::default() },
+/// );
+/// let mut client = NNTPStream::connect_with(addr).expect("Failed to connect");
+/// client.authenticate_sasl_external().expect("Authentication failed");
+/// ```
+pub struct ClientCertificate {
+    cert_chain: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+}
+
+impl ClientCertificate {
+    /// Loads a certificate chain and private key from PEM files.
+    ///
+    /// The certificate file may hold several certificates, starting with the
+    /// client's own. The key may be PKCS#8, PKCS#1 (RSA) or SEC1 (EC).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::TlsError`] if a file cannot be read or contains no
+    /// certificate or key.
+    pub fn from_pem_files(
+        cert_path: impl AsRef<Path>,
+        key_path: impl AsRef<Path>,
+    ) -> errors::Result<Self> {
+        let cert_chain = CertificateDer::pem_file_iter(cert_path.as_ref())
+            .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| pem_error(cert_path.as_ref(), e))?;
+        let key = PrivateKeyDer::from_pem_file(key_path.as_ref())
+            .map_err(|e| pem_error(key_path.as_ref(), e))?;
+        Self::new(cert_chain, key)
+    }
+
+    /// Creates a client certificate from in-memory DER data.
+    ///
+    /// `cert_chain` starts with the client's own certificate. The key format
+    /// (PKCS#8, PKCS#1 or SEC1) is detected automatically.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NNTPError::TlsError`] if the chain is empty or the key format
+    /// is not recognized.
+    pub fn from_der(cert_chain: Vec<Vec<u8>>, key: Vec<u8>) -> errors::Result<Self> {
+        let key = PrivateKeyDer::try_from(key).map_err(|e| NNTPError::TlsError {
+            message: format!("invalid private key: {e}"),
+        })?;
+        Self::new(
+            cert_chain.into_iter().map(CertificateDer::from).collect(),
+            key,
+        )
+    }
+
+    fn new(
+        cert_chain: Vec<CertificateDer<'static>>,
+        key: PrivateKeyDer<'static>,
+    ) -> errors::Result<Self> {
+        if cert_chain.is_empty() {
+            return Err(NNTPError::TlsError {
+                message: "client certificate chain is empty".to_owned(),
+            });
+        }
+        Ok(ClientCertificate { cert_chain, key })
+    }
+}
+
+impl Clone for ClientCertificate {
+    fn clone(&self) -> Self {
+        ClientCertificate {
+            cert_chain: self.cert_chain.clone(),
+            key: self.key.clone_key(),
+        }
+    }
+}
+
+impl fmt::Debug for ClientCertificate {
+    /// Omits the private key.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ClientCertificate")
+            .field("cert_chain", &self.cert_chain)
+            .finish_non_exhaustive()
+    }
+}
+
+fn pem_error(path: &Path, error: rustls::pki_types::pem::Error) -> NNTPError {
+    NNTPError::TlsError {
+        message: format!("failed to load {}: {error}", path.display()),
+    }
 }
 
 /// Builds a dangerous ClientConfig that skips certificate validation.
-fn build_dangerous_config() -> Result<ClientConfig, std::io::Error> {
+fn build_dangerous_config() -> ConfigBuilder<ClientConfig, WantsClientCert> {
     use rustls::SignatureScheme;
     use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
     use rustls::pki_types::{CertificateDer, UnixTime};
@@ -117,24 +244,19 @@ fn build_dangerous_config() -> Result<ClientConfig, std::io::Error> {
 
     let verifier = Arc::new(NoCertificateVerification);
 
-    Ok(ClientConfig::builder()
+    ClientConfig::builder()
         .dangerous()
         .with_custom_certificate_verifier(verifier)
-        .with_no_client_auth())
 }
 
-/// Wraps a TCP stream with TLS if configured, performing the handshake.
+/// Wraps a TCP stream with TLS, performing the handshake.
 pub(crate) fn wrap_tls(
     tcp_stream: TcpStream,
-    server_addr: &ServerAddress,
-) -> Result<StreamOwned<ClientConnection, TcpStream>, std::io::Error> {
-    let tls_config = server_addr
-        .tls
-        .as_ref()
-        .expect("wrap_tls called but TLS is not configured");
-
+    host: &str,
+    tls_config: &TlsConfig,
+) -> std::io::Result<StreamOwned<ClientConnection, TcpStream>> {
     let config = build_client_config(tls_config)?;
-    let server_name = ServerName::try_from(server_addr.host.as_str())
+    let server_name = ServerName::try_from(host)
         .map_err(|e| std::io::Error::other(format!("invalid server name: {e}")))?
         .to_owned();
 
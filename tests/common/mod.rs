@@ -0,0 +1,264 @@
+//! A scripted NNTP server on a local port, shared by the integration tests.
+//!
+//! Each connection is greeted, then every command line is answered from a
+//! table of command prefixes. Commands without an entry get `500`.
+//!
+//! `tests/data` holds the self-signed certificate and key, made for these
+//! tests only, that the server presents after `STARTTLS`.
+
+#![allow(dead_code)]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::{ServerConfig, ServerConnection, StreamOwned};
+
+/// What the server does when a command matches a prefix.
+#[derive(Debug, Clone)]
+struct Exchange {
+    reply: Vec<u8>,
+    /// Read a dot-terminated data block after `reply`, then send this.
+    after_data: Option<Vec<u8>>,
+    /// Start the TLS handshake after `reply`.
+    starts_tls: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Route {
+    prefix: String,
+    exchanges: Vec<Exchange>,
+    used: usize,
+}
+
+/// Builds the command table of a [`Server`].
+///
+/// Several replies for the same prefix are used in order, the last one for
+/// every later match, across all connections. The first prefix a command
+/// starts with wins.
+#[derive(Debug, Clone)]
+pub struct FakeServer {
+    greetings: Vec<Vec<u8>>,
+    connections: usize,
+    routes: Vec<Route>,
+}
+
+impl FakeServer {
+    pub fn new() -> Self {
+        FakeServer {
+            greetings: Vec::new(),
+            connections: 0,
+            routes: Vec::new(),
+        }
+    }
+
+    /// Greets the next connection with `greeting` instead of `200`. Like
+    /// replies, greetings are used in order and the last one repeats.
+    pub fn greeting(mut self, greeting: impl AsRef<[u8]>) -> Self {
+        self.greetings.push(greeting.as_ref().to_vec());
+        self
+    }
+
+    /// Answers commands starting with `prefix` with `reply`, sent as is. An
+    /// empty reply sends nothing, leaving the answer to a later command.
+    pub fn reply(self, prefix: &str, reply: impl AsRef<[u8]>) -> Self {
+        self.exchange(prefix, reply.as_ref().to_vec(), None)
+    }
+
+    /// Answers commands starting with `prefix` with `reply`, then takes the
+    /// server side of a TLS handshake and goes on over TLS.
+    pub fn reply_then_tls(mut self, prefix: &str, reply: impl AsRef<[u8]>) -> Self {
+        self = self.exchange(prefix, reply.as_ref().to_vec(), None);
+        let route = self.routes.iter_mut().find(|r| r.prefix == prefix).unwrap();
+        route.exchanges.last_mut().unwrap().starts_tls = true;
+        self
+    }
+
+    /// Answers commands starting with `prefix` with `reply`, then reads a
+    /// data block such as an article and answers it with `after_data`.
+    pub fn reply_to_data(
+        self,
+        prefix: &str,
+        reply: impl AsRef<[u8]>,
+        after_data: impl AsRef<[u8]>,
+    ) -> Self {
+        self.exchange(
+            prefix,
+            reply.as_ref().to_vec(),
+            Some(after_data.as_ref().to_vec()),
+        )
+    }
+
+    fn exchange(mut self, prefix: &str, reply: Vec<u8>, after_data: Option<Vec<u8>>) -> Self {
+        let exchange = Exchange {
+            reply,
+            after_data,
+            starts_tls: false,
+        };
+        match self.routes.iter_mut().find(|route| route.prefix == prefix) {
+            Some(route) => route.exchanges.push(exchange),
+            None => self.routes.push(Route {
+                prefix: prefix.to_owned(),
+                exchanges: vec![exchange],
+                used: 0,
+            }),
+        }
+        self
+    }
+
+    /// Listens on a free local port.
+    pub fn start(self) -> Server {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let (command_sender, commands) = mpsc::channel();
+        let (data_sender, data) = mpsc::channel();
+        let script = Arc::new(Mutex::new(self));
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { break };
+                let script = script.clone();
+                let command_sender = command_sender.clone();
+                let data_sender = data_sender.clone();
+                thread::spawn(move || serve(&script, stream, command_sender, data_sender));
+            }
+        });
+        Server {
+            addr,
+            commands,
+            data,
+        }
+    }
+
+    fn next_greeting(&mut self) -> Vec<u8> {
+        let greeting = match self.greetings.len() {
+            0 => b"200 test server ready\r\n".to_vec(),
+            n => self.greetings[self.connections.min(n - 1)].clone(),
+        };
+        self.connections += 1;
+        greeting
+    }
+
+    fn next_exchange(&mut self, command: &str) -> Option<Exchange> {
+        let route = self
+            .routes
+            .iter_mut()
+            .find(|route| command.starts_with(&route.prefix))?;
+        let exchange = route.exchanges[route.used.min(route.exchanges.len() - 1)].clone();
+        route.used += 1;
+        Some(exchange)
+    }
+}
+
+fn serve(
+    script: &Mutex<FakeServer>,
+    mut stream: TcpStream,
+    commands: Sender<String>,
+    data: Sender<Vec<u8>>,
+) {
+    let greeting = script.lock().unwrap().next_greeting();
+    if stream.write_all(&greeting).is_err() {
+        return;
+    }
+
+    let mut reader = BufReader::new(stream);
+    if !converse(script, &mut reader, &commands, &data) {
+        return;
+    }
+    // the client waits for the reply before its handshake, so nothing is
+    // left in the buffer
+    let connection = ServerConnection::new(tls_config()).unwrap();
+    let mut reader = BufReader::new(StreamOwned::new(connection, reader.into_inner()));
+    converse(script, &mut reader, &commands, &data);
+}
+
+// Answers commands until the client disconnects, or returns true when the
+// client is to start TLS.
+fn converse<S: Read + Write>(
+    script: &Mutex<FakeServer>,
+    reader: &mut BufReader<S>,
+    commands: &Sender<String>,
+    data: &Sender<Vec<u8>>,
+) -> bool {
+    let mut command = String::new();
+    while reader.read_line(&mut command).unwrap_or(0) > 0 {
+        // recorded before replying, so the client sees it once it has a reply
+        let _ = commands.send(command.trim_end().to_owned());
+        let exchange = script.lock().unwrap().next_exchange(&command);
+        command.clear();
+
+        let Some(exchange) = exchange else {
+            if reader
+                .get_mut()
+                .write_all(b"500 unknown command\r\n")
+                .is_err()
+            {
+                return false;
+            }
+            continue;
+        };
+        if reader.get_mut().write_all(&exchange.reply).is_err() {
+            return false;
+        }
+        if let Some(after_data) = exchange.after_data {
+            let _ = data.send(read_data_block(reader));
+            if reader.get_mut().write_all(&after_data).is_err() {
+                return false;
+            }
+        }
+        if exchange.starts_tls {
+            return true;
+        }
+    }
+    false
+}
+
+fn tls_config() -> Arc<ServerConfig> {
+    let certs = CertificateDer::pem_slice_iter(include_bytes!("../data/cert.pem"))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let key = PrivateKeyDer::from_pem_slice(include_bytes!("../data/key.pem")).unwrap();
+    let config = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .unwrap();
+    Arc::new(config)
+}
+
+/// Reads up to and including the `.` line that ends a data block.
+fn read_data_block(reader: &mut impl BufRead) -> Vec<u8> {
+    let mut block = Vec::new();
+    loop {
+        let start = block.len();
+        if reader.read_until(b'\n', &mut block).unwrap_or(0) == 0 || &block[start..] == b".\r\n" {
+            return block;
+        }
+    }
+}
+
+/// A running [`FakeServer`].
+pub struct Server {
+    addr: String,
+    commands: Receiver<String>,
+    data: Receiver<Vec<u8>>,
+}
+
+impl Server {
+    /// The `host:port` to connect to.
+    pub fn addr(&self) -> String {
+        self.addr.clone()
+    }
+
+    /// The command lines received since the last call, without line endings.
+    pub fn commands(&self) -> Vec<String> {
+        self.commands.try_iter().collect()
+    }
+
+    /// The data blocks received since the last call, with their `.` lines.
+    pub fn data(&self) -> Vec<Vec<u8>> {
+        self.data.try_iter().collect()
+    }
+}
@@ -0,0 +1,80 @@
+//! Drives dot-stuffed multi-line responses through `NNTPStream`, against a
+//! local server thread, with raw mode off and on.
+
+mod common;
+
+use common::{FakeServer, Server};
+use nntp::NNTPStream;
+
+/// A body as sent on the wire: lines beginning with a dot are stuffed,
+/// except `.unstuffed`, as some servers forget to.
+const WIRE_BODY: &[&str] = &[
+    "..\r\n",
+    "...\r\n",
+    "..leading dot\r\n",
+    "....yEnc\r\n",
+    ".unstuffed\r\n",
+    "dots.. in the middle.\r\n",
+];
+
+fn start_server() -> Server {
+    let mut body = b"222 0 <dots@example.com>\r\n".to_vec();
+    body.extend_from_slice(WIRE_BODY.concat().as_bytes());
+    body.extend_from_slice(b".\r\n");
+    FakeServer::new()
+        .reply("BODY", body)
+        .reply("DATE", "111 20240101000000\r\n")
+        .start()
+}
+
+fn unstuffed_body() -> Vec<String> {
+    vec![
+        ".\r\n".to_string(),
+        "..\r\n".to_string(),
+        ".leading dot\r\n".to_string(),
+        "...yEnc\r\n".to_string(),
+        ".unstuffed\r\n".to_string(),
+        "dots.. in the middle.\r\n".to_string(),
+    ]
+}
+
+#[test]
+fn multiline_responses_are_unstuffed() {
+    let mut client = NNTPStream::connect(start_server().addr()).unwrap();
+    assert!(!client.raw_mode());
+
+    assert_eq!(
+        client.body_by_id("<dots@example.com>").unwrap(),
+        unstuffed_body()
+    );
+    let streamed: Vec<String> = client
+        .body_stream("<dots@example.com>")
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(streamed, unstuffed_body());
+
+    // the lone "." ended the response, so the next command reads its own reply
+    assert_eq!(client.date().unwrap().year, 2024);
+}
+
+#[test]
+fn raw_mode_keeps_lines_as_sent() {
+    let mut client = NNTPStream::connect(start_server().addr()).unwrap();
+    client.set_raw_mode(true);
+
+    let wire: Vec<String> = WIRE_BODY.iter().map(|line| line.to_string()).collect();
+    assert_eq!(client.body_by_id("<dots@example.com>").unwrap(), wire);
+    let streamed: Vec<String> = client
+        .body_stream("<dots@example.com>")
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(streamed, wire);
+    assert_eq!(
+        client.body_bytes("<dots@example.com>").unwrap(),
+        WIRE_BODY.concat().into_bytes()
+    );
+
+    assert_eq!(client.date().unwrap().year, 2024);
+}
@@ -0,0 +1,34 @@
+//! Drives the `XHDR` fallback of `NNTPStream::hdr_by_id` against a local
+//! server thread that does not know `HDR`.
+
+mod common;
+
+use common::FakeServer;
+use nntp::{NNTPError, NNTPStream};
+
+#[test]
+fn xhdr_fallback_by_message_id() {
+    let server = FakeServer::new()
+        .reply(
+            "XHDR Subject <1@example.com>",
+            "221 Subject header follows\r\n<1@example.com> Hello, world\r\n.\r\n",
+        )
+        .start();
+    let mut client = NNTPStream::connect(server.addr()).unwrap();
+
+    assert_eq!(
+        client.hdr_by_id("Subject", "<1@example.com>").unwrap(),
+        "Hello, world"
+    );
+    let sent = server.commands();
+    assert_eq!(sent.last().unwrap(), "XHDR Subject <1@example.com>");
+
+    for field in [":bytes", ":lines"] {
+        match client.hdr_by_id(field, "<1@example.com>") {
+            Err(NNTPError::Unsupported { .. }) => (),
+            other => panic!("expected an unsupported error, got {:?}", other),
+        }
+    }
+    // nothing is sent, in particular no XOVER with a message ID
+    assert_eq!(server.commands().len(), 0);
+}
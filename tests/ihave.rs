@@ -0,0 +1,61 @@
+//! Drives `NNTPStream::ihave` against the fake server.
+
+mod common;
+
+use common::FakeServer;
+use nntp::{IhaveOutcome, NNTPError, NNTPStream};
+
+const ARTICLE: &str = "Message-ID: <a@example.com>\r\n\r\n..dotted\r\n.\r\n";
+
+fn offer(after_data: &str) -> (IhaveOutcome, Vec<Vec<u8>>) {
+    let server = FakeServer::new()
+        .reply_to_data("IHAVE", "335 send it\r\n", after_data)
+        .start();
+    let mut client = NNTPStream::connect(server.addr()).unwrap();
+    let outcome = client.ihave("<a@example.com>", ARTICLE).unwrap();
+    (outcome, server.data())
+}
+
+#[test]
+fn transfer_outcomes() {
+    for (reply, expected) in [
+        ("235 thanks\r\n", IhaveOutcome::Accepted),
+        ("436 try again later\r\n", IhaveOutcome::TryLater),
+        ("437 rejected\r\n", IhaveOutcome::Rejected),
+    ] {
+        let (outcome, data) = offer(reply);
+        assert_eq!(outcome, expected);
+        assert_eq!(data, [ARTICLE.as_bytes()]);
+    }
+}
+
+#[test]
+fn unwanted_article_is_not_sent() {
+    let server = FakeServer::new()
+        .reply("IHAVE <a@example.com>", "435 already have it\r\n")
+        .reply("IHAVE <b@example.com>", "436 not now\r\n")
+        .reply("DATE", "111 20240101000000\r\n")
+        .start();
+    let mut client = NNTPStream::connect(server.addr()).unwrap();
+
+    let outcome = client.ihave("<a@example.com>", ARTICLE).unwrap();
+    assert_eq!(outcome, IhaveOutcome::NotWanted);
+    let outcome = client.ihave("<b@example.com>", ARTICLE).unwrap();
+    assert_eq!(outcome, IhaveOutcome::TryLater);
+
+    // had the article been sent, DATE would read the wrong line
+    assert_eq!(client.date().unwrap().year, 2024);
+    assert!(server.data().is_empty());
+}
+
+#[test]
+fn unterminated_article_is_refused() {
+    let server = FakeServer::new().start();
+    let mut client = NNTPStream::connect(server.addr()).unwrap();
+
+    match client.ihave("<a@example.com>", "no terminator\r\n") {
+        Err(NNTPError::InvalidMessage { .. }) => (),
+        other => panic!("expected an invalid message, got {:?}", other),
+    }
+    assert!(server.commands().is_empty());
+}
@@ -0,0 +1,50 @@
+//! Checks that `LIST` variants skip rows they cannot parse instead of
+//! failing the whole listing.
+
+mod common;
+
+use common::FakeServer;
+use nntp::{NNTPError, NNTPStream, NewsGroup};
+
+#[test]
+fn bad_rows_are_skipped() {
+    let server = FakeServer::new()
+        .reply(
+            "LIST COUNTS",
+            "215 list follows\r\n\
+             misc.test 3002322 3000234 1234 y\r\n\
+             misc.broken 3002322 many 1234 y\r\n\
+             alt.test 10 1 9 m\r\n.\r\n",
+        )
+        .reply(
+            "LIST ACTIVE.TIMES",
+            "215 list follows\r\n\
+             misc.test 930445408 <creatme@isc.org>\r\n\
+             misc.broken yesterday <creatme@isc.org>\r\n.\r\n",
+        )
+        .reply(
+            "LIST DISTRIB.PATS",
+            "215 list follows\r\n10:local.*:local\r\nno weight here\r\n.\r\n",
+        )
+        .start();
+    let mut client = NNTPStream::connect(server.addr()).unwrap();
+
+    let names: Vec<String> = client
+        .list_counts(None)
+        .unwrap()
+        .into_iter()
+        .map(|group| group.name)
+        .collect();
+    assert_eq!(names, ["misc.test", "alt.test"]);
+    assert_eq!(client.list_active_times(None).unwrap().len(), 1);
+    assert_eq!(client.list_distrib_pats().unwrap()[0].distribution, "local");
+}
+
+#[test]
+fn bad_rows_are_parse_errors() {
+    let line = "misc.broken 3002322 many 1234 y\r\n";
+    match NewsGroup::from_counts_response(line) {
+        Err(NNTPError::ParseError { line: bad_line, .. }) => assert_eq!(bad_line, line),
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+}
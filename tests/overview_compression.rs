@@ -0,0 +1,148 @@
+//! Drives `XZVER` and `XFEATURE COMPRESS GZIP` overview responses through
+//! `NNTPStream`, against a local server thread that compresses them.
+
+mod common;
+
+use std::io::Write;
+
+use common::FakeServer;
+use flate2::Compression;
+use flate2::write::{DeflateEncoder, ZlibEncoder};
+use nntp::{NNTPStream, OverviewCompression};
+
+const OVERVIEW: &[&str] = &[
+    "1\tFirst post\talice@example.com\t1 Jan 2024 00:00:00 +0000\t<1@example.com>\t\t1024\t20\r\n",
+    "2\tRe: First post\tbob@example.com\t1 Jan 2024 01:00:00 +0000\t<2@example.com>\t<1@example.com>\t2048\t40\r\n",
+    "3\t.leading dot\tcarol@example.com\t2 Jan 2024 00:00:00 +0000\t<3@example.com>\t\t512\t10\r\n",
+];
+
+const OVERVIEW_FMT: &str = "215 Order of fields in overview database\r\n\
+     Subject:\r\nFrom:\r\nDate:\r\nMessage-ID:\r\nReferences:\r\n\
+     :bytes\r\n:lines\r\n.\r\n";
+
+fn start_server(xzver: bool) -> String {
+    let mut server = FakeServer::new()
+        .reply("LIST OVERVIEW.FMT", OVERVIEW_FMT)
+        .reply(
+            "XOVER",
+            format!(
+                "224 Overview information follows\r\n{}.\r\n",
+                OVERVIEW.concat()
+            ),
+        );
+    if xzver {
+        let mut reply = b"224 Overview information follows (compressed)\r\n".to_vec();
+        reply.extend(xzver_body().concat());
+        reply.extend_from_slice(b".\r\n");
+        server = server.reply("XZVER", reply);
+    }
+    server.start().addr()
+}
+
+// Answers OVER with `over`, after XFEATURE COMPRESS GZIP is accepted.
+fn start_gzip_server(over: Vec<u8>) -> String {
+    FakeServer::new()
+        .reply("LIST OVERVIEW.FMT", OVERVIEW_FMT)
+        .reply("XFEATURE COMPRESS GZIP", "290 feature enabled\r\n")
+        .reply("OVER", over)
+        .start()
+        .addr()
+}
+
+/// The overview as raw DEFLATE, yEnc-encoded and dot-stuffed, one line per
+/// element.
+///
+/// Lines are broken before every `.`, so the client has to unstuff lines
+/// that the encoder left starting with a dot.
+fn xzver_body() -> Vec<Vec<u8>> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(OVERVIEW.concat().as_bytes()).unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let mut encoded = vec![b"=ybegin line=128 size=0 name=xzver\r\n".to_vec()];
+    let mut line = Vec::new();
+    for byte in compressed {
+        let byte = byte.wrapping_add(42);
+        if byte == b'.' || line.len() >= 128 {
+            encoded.push(end_line(&mut line));
+        }
+        if matches!(byte, 0 | b'\n' | b'\r' | b'=') {
+            line.push(b'=');
+            line.push(byte.wrapping_add(64));
+        } else {
+            line.push(byte);
+        }
+    }
+    encoded.push(end_line(&mut line));
+    encoded.push(b"=yend size=0\r\n".to_vec());
+
+    assert!(encoded.iter().any(|line| line.starts_with(b"..")));
+    encoded
+}
+
+fn end_line(line: &mut Vec<u8>) -> Vec<u8> {
+    let mut stuffed = Vec::with_capacity(line.len() + 3);
+    if line.first() == Some(&b'.') {
+        stuffed.push(b'.');
+    }
+    stuffed.append(line);
+    stuffed.extend_from_slice(b"\r\n");
+    stuffed
+}
+
+fn assert_overview(client: &mut NNTPStream) {
+    let entries = client.over(1..=3).unwrap();
+    let subjects: Vec<&str> = entries.iter().map(|e| e.subject.as_str()).collect();
+    assert_eq!(subjects, ["First post", "Re: First post", ".leading dot"]);
+    assert_eq!(entries[1].references, "<1@example.com>");
+    assert_eq!(entries[1].bytes, Some(2048));
+    assert_eq!(entries[2].lines, Some(10));
+}
+
+#[test]
+fn xzver_overview_is_decoded() {
+    let mut client = NNTPStream::connect(start_server(true)).unwrap();
+    client
+        .enable_overview_compression(OverviewCompression::Xzver)
+        .unwrap();
+    assert_overview(&mut client);
+
+    // leading dots in the yEnc data are unstuffed in raw mode too
+    client.set_raw_mode(true);
+    assert_overview(&mut client);
+}
+
+#[test]
+fn xzver_falls_back_to_xover() {
+    let mut client = NNTPStream::connect(start_server(false)).unwrap();
+    client
+        .enable_overview_compression(OverviewCompression::Xzver)
+        .unwrap();
+    assert_overview(&mut client);
+    assert_overview(&mut client);
+}
+
+#[test]
+fn gzip_overview_is_inflated() {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(OVERVIEW.concat().as_bytes()).unwrap();
+    encoder.write_all(b".\r\n").unwrap();
+    let mut reply = b"224 Overview information follows [COMPRESS=GZIP]\r\n".to_vec();
+    reply.extend(encoder.finish().unwrap());
+
+    let mut client = NNTPStream::connect(start_gzip_server(reply)).unwrap();
+    client
+        .enable_overview_compression(OverviewCompression::Gzip)
+        .unwrap();
+    assert_overview(&mut client);
+}
+
+#[test]
+fn compress_marker_is_ignored_unless_enabled() {
+    let reply = format!(
+        "224 Overview follows, COMPRESS=GZIP available\r\n{}.\r\n",
+        OVERVIEW.concat()
+    );
+    let mut client = NNTPStream::connect(start_gzip_server(reply.into_bytes())).unwrap();
+    assert_overview(&mut client);
+}
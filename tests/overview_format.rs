@@ -0,0 +1,65 @@
+//! Checks when the overview format falls back to the RFC 3977 default,
+//! against a local server thread.
+
+mod common;
+
+use common::{FakeServer, Server};
+use nntp::{NNTPError, NNTPStream};
+
+/// Refuses `LIST OVERVIEW.FMT` once with `refusal`, then answers with a
+/// format that has an extra `Xref:full` field.
+fn start_server(refusal: &str) -> Server {
+    FakeServer::new()
+        .reply("LIST OVERVIEW.FMT", refusal)
+        .reply(
+            "LIST OVERVIEW.FMT",
+            "215 Order of fields in overview database\r\n\
+             Subject:\r\nFrom:\r\nDate:\r\nMessage-ID:\r\nReferences:\r\n\
+             :bytes\r\n:lines\r\nXref:full\r\n.\r\n",
+        )
+        .reply(
+            "OVER",
+            "224 Overview information follows\r\n\
+             1\tSubject\tfrom@example.com\t1 Jan 2024 00:00:00 +0000\t<1@example.com>\t\t\
+             100\t2\tXref: news.example.com misc.test:1\r\n.\r\n",
+        )
+        .start()
+}
+
+/// How often the client asked for the overview format.
+fn format_requests(server: &Server) -> usize {
+    server
+        .commands()
+        .iter()
+        .filter(|command| command.as_str() == "LIST OVERVIEW.FMT")
+        .count()
+}
+
+#[test]
+fn auth_required_is_not_cached() {
+    let server = start_server("480 authentication required\r\n");
+    let mut client = NNTPStream::connect(server.addr()).unwrap();
+
+    match client.over(1..=1) {
+        Err(NNTPError::ResponseCode { received: 480, .. }) => (),
+        other => panic!("expected a 480 error, got {:?}", other),
+    }
+
+    let entries = client.over(1..=1).unwrap();
+    assert_eq!(format_requests(&server), 2);
+    assert_eq!(client.list_overview_fmt().unwrap().fields().len(), 8);
+    assert_eq!(entries[0].extra["Xref"], "news.example.com misc.test:1");
+}
+
+#[test]
+fn unsupported_command_uses_default_format() {
+    for refusal in ["500 unknown command\r\n", "503 not supported\r\n"] {
+        let server = start_server(refusal);
+        let mut client = NNTPStream::connect(server.addr()).unwrap();
+
+        let entries = client.over(1..=1).unwrap();
+        assert_eq!(entries[0].lines, Some(2));
+        client.over(1..=1).unwrap();
+        assert_eq!(format_requests(&server), 1);
+    }
+}
@@ -0,0 +1,74 @@
+//! Drives the pipelined `*_many` commands against the fake server.
+
+mod common;
+
+use common::FakeServer;
+use nntp::errors::check_network_error;
+use nntp::{NNTPError, NNTPStream};
+
+fn start_server() -> common::Server {
+    FakeServer::new()
+        .reply(
+            "ARTICLE 1",
+            "220 1 <1@example.com>\r\nSubject: one\r\n\r\nbody\r\n.\r\n",
+        )
+        .reply("HEAD 1", "221 1 <1@example.com>\r\nSubject: one\r\n.\r\n")
+        .reply("BODY 1", "222 1 <1@example.com>\r\nbody\r\n.\r\n")
+        .reply("STAT 1", "223 1 <1@example.com>\r\n")
+        .reply("ARTICLE 2", "423 no such article\r\n")
+        .reply("HEAD 2", "423 no such article\r\n")
+        .reply("BODY 2", "423 no such article\r\n")
+        .reply("STAT 2", "423 no such article\r\n")
+        .reply("HEAD 3", "garbage\r\n")
+        .reply("DATE", "111 20240101000000\r\n")
+        .start()
+}
+
+fn assert_unavailable<T: std::fmt::Debug>(result: &Result<T, NNTPError>) {
+    match result {
+        Err(NNTPError::ArticleUnavailable) => (),
+        other => panic!("expected ArticleUnavailable, got {:?}", other),
+    }
+}
+
+#[test]
+fn missing_articles_fail_their_own_entry() {
+    let server = start_server();
+    let mut client = NNTPStream::connect(server.addr()).unwrap();
+
+    let articles = client.article_many(&[1, 2]).unwrap();
+    assert!(articles[0].is_ok());
+    assert_unavailable(&articles[1]);
+
+    let heads = client.head_many(&[1, 2]).unwrap();
+    assert_eq!(heads[0].as_ref().unwrap().get("Subject"), Some("one"));
+    assert_unavailable(&heads[1]);
+
+    let bodies = client.body_many(&[1, 2]).unwrap();
+    assert_eq!(bodies[0].as_ref().unwrap(), &["body\r\n"]);
+    assert_unavailable(&bodies[1]);
+
+    let stats = client.stat_many(&[1, 2]).unwrap();
+    assert_eq!(stats[0].as_ref().unwrap(), "1 <1@example.com>");
+    assert_unavailable(&stats[1]);
+
+    assert_eq!(client.date().unwrap().year, 2024);
+}
+
+#[test]
+fn unreadable_response_closes_the_connection() {
+    let server = start_server();
+    let mut client = NNTPStream::connect(server.addr()).unwrap();
+
+    match client.head_many(&[1, 3, 1, 1]) {
+        Err(NNTPError::InvalidResponse { .. }) => (),
+        other => panic!("expected an invalid response, got {:?}", other),
+    }
+
+    // the responses to the last two HEAD commands must not answer DATE
+    let error = client.date().unwrap_err();
+    assert!(check_network_error(&error), "{:?}", error);
+
+    client.re_connect().unwrap();
+    assert_eq!(client.date().unwrap().year, 2024);
+}
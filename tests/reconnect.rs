@@ -0,0 +1,57 @@
+//! Drives `NNTPStream::re_connect` against the fake server.
+
+mod common;
+
+use common::FakeServer;
+use nntp::{NNTPError, NNTPStream, OverviewCompression};
+
+#[test]
+fn greeting_error_is_returned_before_authenticating() {
+    let server = FakeServer::new()
+        .greeting("200 test server ready\r\n")
+        .greeting("400 service temporarily unavailable\r\n")
+        .greeting("200 test server ready\r\n")
+        .reply("AUTHINFO USER", "381 password required\r\n")
+        .reply("AUTHINFO PASS", "281 authentication accepted\r\n")
+        .start();
+    let mut client = NNTPStream::connect(server.addr()).unwrap();
+    client.user_password_authenticate("user", "secret").unwrap();
+    server.commands();
+
+    match client.re_connect() {
+        Err(NNTPError::FailedConnecting { expected, .. }) => {
+            assert_eq!(expected, "greeting response")
+        }
+        other => panic!("expected a greeting error, got {:?}", other),
+    }
+    assert!(server.commands().is_empty());
+
+    // the credentials are still there for the next attempt
+    client.re_connect().unwrap();
+    assert_eq!(
+        server.commands(),
+        ["AUTHINFO USER user", "AUTHINFO PASS secret"]
+    );
+}
+
+#[test]
+fn overview_compression_survives_a_refusal() {
+    let server = FakeServer::new()
+        .reply("XFEATURE COMPRESS GZIP", "290 feature enabled\r\n")
+        .reply("XFEATURE COMPRESS GZIP", "503 not now\r\n")
+        .reply("XFEATURE COMPRESS GZIP", "290 feature enabled\r\n")
+        .start();
+    let mut client = NNTPStream::connect(server.addr()).unwrap();
+    client
+        .enable_overview_compression(OverviewCompression::Gzip)
+        .unwrap();
+
+    match client.re_connect() {
+        Err(NNTPError::ResponseCode { received: 503, .. }) => (),
+        other => panic!("expected a 503 error, got {:?}", other),
+    }
+    server.commands();
+
+    client.re_connect().unwrap();
+    assert_eq!(server.commands(), ["XFEATURE COMPRESS GZIP"]);
+}
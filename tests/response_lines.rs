@@ -0,0 +1,50 @@
+//! Drops `ResponseLines` iterators partway through a response, against a
+//! local server thread, and checks that the connection is still in step.
+
+mod common;
+
+use common::FakeServer;
+use nntp::NNTPStream;
+
+const LINES: usize = 2000;
+
+fn start_server() -> String {
+    let mut article = b"220 1 <large@example.com>\r\nSubject: large\r\n\r\n".to_vec();
+    let mut body = b"222 1 <large@example.com>\r\n".to_vec();
+    for n in 0..LINES {
+        let line = format!("line {} of a body larger than the read buffer\r\n", n);
+        article.extend_from_slice(line.as_bytes());
+        body.extend_from_slice(line.as_bytes());
+    }
+    article.extend_from_slice(b".\r\n");
+    body.extend_from_slice(b".\r\n");
+    FakeServer::new()
+        .reply("ARTICLE", article)
+        .reply("BODY", body)
+        .reply("DATE", "111 20240101000000\r\n")
+        .start()
+        .addr()
+}
+
+#[test]
+fn dropped_stream_is_read_to_the_end() {
+    let mut client = NNTPStream::connect(start_server()).unwrap();
+
+    let mut lines = client.article_stream("<large@example.com>").unwrap();
+    assert_eq!(lines.next().unwrap().unwrap(), "Subject: large\r\n");
+    drop(lines);
+    assert_eq!(client.date().unwrap().year, 2024);
+
+    let first: Vec<String> = client
+        .body_stream(1)
+        .unwrap()
+        .take(10)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(first[9], "line 9 of a body larger than the read buffer\r\n");
+    assert_eq!(client.date().unwrap().year, 2024);
+
+    // the rest of the body was discarded, not left for the next command
+    let body = client.body_by_number(1).unwrap();
+    assert_eq!(body.len(), LINES);
+}
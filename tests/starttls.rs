@@ -0,0 +1,58 @@
+//! Drives `STARTTLS` (RFC 4642) against the fake server, which answers with
+//! a TLS handshake using a self-signed certificate.
+
+mod common;
+
+use common::FakeServer;
+use nntp::{NNTPError, NNTPStream, ServerAddress, TlsConfig};
+
+const CAPABILITIES_PLAIN: &str = "101 Capability list:\r\nVERSION 2\r\nREADER\r\nSTARTTLS\r\n.\r\n";
+const CAPABILITIES_TLS: &str =
+    "101 Capability list:\r\nVERSION 2\r\nREADER\r\nAUTHINFO USER\r\n.\r\n";
+
+#[test]
+fn refused_when_not_advertised() {
+    let server = FakeServer::new()
+        .reply(
+            "CAPABILITIES",
+            "101 Capability list:\r\nVERSION 2\r\nREADER\r\n.\r\n",
+        )
+        .reply_then_tls("STARTTLS", "382 continue with TLS negotiation\r\n")
+        .start();
+    let mut client = NNTPStream::connect(server.addr()).unwrap();
+
+    match client.starttls() {
+        Err(NNTPError::TlsError { message }) => assert!(message.contains("STARTTLS")),
+        other => panic!("expected a TLS error, got {:?}", other),
+    }
+    assert_eq!(server.commands(), ["CAPABILITIES"]);
+}
+
+#[test]
+fn capabilities_are_fetched_again_over_tls() {
+    let server = FakeServer::new()
+        .reply("CAPABILITIES", CAPABILITIES_PLAIN)
+        .reply("CAPABILITIES", CAPABILITIES_TLS)
+        .reply_then_tls("STARTTLS", "382 continue with TLS negotiation\r\n")
+        .start();
+    let port = server.addr().rsplit_once(':').unwrap().1.parse().unwrap();
+    let tls = TlsConfig {
+        danger_accept_invalid_certs: true,
+        ..Default::default()
+    };
+    let mut client =
+        NNTPStream::connect_with(ServerAddress::with_starttls("127.0.0.1", port, tls)).unwrap();
+
+    // the capabilities read in plain text were dropped after the upgrade,
+    // and nothing was fetched until needed
+    assert_eq!(server.commands(), ["CAPABILITIES", "STARTTLS"]);
+    let capabilities = client.capabilities().unwrap();
+    assert!(!capabilities.starttls);
+    assert_eq!(capabilities.authinfo, ["USER"]);
+    assert_eq!(server.commands(), ["CAPABILITIES"]);
+
+    match client.starttls() {
+        Err(NNTPError::TlsError { .. }) => (),
+        other => panic!("expected a TLS error, got {:?}", other),
+    }
+}
@@ -0,0 +1,130 @@
+//! Drives `StreamingSession` (RFC 4644) against the fake server.
+
+mod common;
+
+use common::{FakeServer, Server};
+use nntp::streaming::{StreamOutcome, StreamResponse};
+use nntp::{NNTPError, NNTPStream};
+
+const ARTICLE: &str = "Message-ID: <a@example.com>\r\n\r\nbody\r\n.\r\n";
+
+fn streaming_server() -> FakeServer {
+    FakeServer::new()
+        .reply("MODE STREAM", "203 streaming permitted\r\n")
+        .reply("DATE", "111 20240101000000\r\n")
+}
+
+fn connect(server: &Server) -> NNTPStream {
+    NNTPStream::connect(server.addr()).unwrap()
+}
+
+fn response(message_id: &str, outcome: StreamOutcome) -> StreamResponse {
+    StreamResponse {
+        message_id: message_id.to_owned(),
+        outcome,
+    }
+}
+
+#[test]
+fn replies_map_to_outcomes() {
+    let server = streaming_server()
+        .reply("CHECK <a@example.com>", "238 <a@example.com>\r\n")
+        .reply("CHECK <b@example.com>", "431 <b@example.com>\r\n")
+        .reply("CHECK <c@example.com>", "438 <c@example.com>\r\n")
+        .reply_to_data("TAKETHIS <a@example.com>", "", "239 <a@example.com>\r\n")
+        .reply_to_data("TAKETHIS <d@example.com>", "", "439 <d@example.com>\r\n")
+        .start();
+    let mut client = connect(&server);
+    let mut session = client.mode_stream().unwrap();
+
+    for message_id in ["<a@example.com>", "<b@example.com>", "<c@example.com>"] {
+        session.check(message_id).unwrap();
+    }
+    session.takethis("<a@example.com>", ARTICLE).unwrap();
+    session.takethis("<d@example.com>", ARTICLE).unwrap();
+
+    let responses: Vec<StreamResponse> = session.by_ref().map(|r| r.unwrap()).collect();
+    assert_eq!(
+        responses,
+        [
+            response("<a@example.com>", StreamOutcome::Wanted),
+            response("<b@example.com>", StreamOutcome::TryLater),
+            response("<c@example.com>", StreamOutcome::NotWanted),
+            response("<a@example.com>", StreamOutcome::Transferred),
+            response("<d@example.com>", StreamOutcome::Rejected),
+        ]
+    );
+    drop(session);
+    assert_eq!(server.data(), [ARTICLE.as_bytes(), ARTICLE.as_bytes()]);
+}
+
+#[test]
+fn responses_are_matched_by_message_id_when_the_window_is_full() {
+    // <a> is only answered after <b>
+    let server = streaming_server()
+        .reply("CHECK <a@example.com>", "")
+        .reply(
+            "CHECK <b@example.com>",
+            "438 <b@example.com>\r\n238 <a@example.com>\r\n",
+        )
+        .reply("CHECK <c@example.com>", "238 <c@example.com>\r\n")
+        .start();
+    let mut client = connect(&server);
+    let mut session = client.mode_stream().unwrap().with_window(2);
+
+    session.check("<a@example.com>").unwrap();
+    session.check("<b@example.com>").unwrap();
+    // makes room by reading the first response, which is about <b>
+    session.check("<c@example.com>").unwrap();
+    assert_eq!(session.in_flight(), 2);
+
+    assert_eq!(
+        session.finish().unwrap(),
+        [
+            response("<b@example.com>", StreamOutcome::NotWanted),
+            response("<a@example.com>", StreamOutcome::Wanted),
+            response("<c@example.com>", StreamOutcome::Wanted),
+        ]
+    );
+}
+
+#[test]
+fn reply_for_unknown_message_id_is_rejected() {
+    let server = streaming_server()
+        .reply("CHECK <a@example.com>", "238 <other@example.com>\r\n")
+        .start();
+    let mut client = connect(&server);
+    let mut session = client.mode_stream().unwrap();
+
+    session.check("<a@example.com>").unwrap();
+    match session.next() {
+        Some(Err(NNTPError::InvalidResponse { response })) => {
+            assert_eq!(response, "238 <other@example.com>")
+        }
+        other => panic!("expected an invalid response, got {:?}", other),
+    }
+    assert!(session.next().is_none());
+    drop(session);
+
+    assert_eq!(client.date().unwrap().year, 2024);
+}
+
+#[test]
+fn drop_reads_outstanding_responses() {
+    let server = streaming_server()
+        .reply("CHECK <a@example.com>", "238 <a@example.com>\r\n")
+        .reply("CHECK <b@example.com>", "438 <b@example.com>\r\n")
+        .reply_to_data("TAKETHIS <a@example.com>", "", "239 <a@example.com>\r\n")
+        .start();
+    let mut client = connect(&server);
+    let mut session = client.mode_stream().unwrap();
+
+    session.check("<a@example.com>").unwrap();
+    session.check("<b@example.com>").unwrap();
+    session.takethis("<a@example.com>", ARTICLE).unwrap();
+    assert_eq!(session.in_flight(), 3);
+    drop(session);
+
+    // DATE reads its own reply, not one left over from the session
+    assert_eq!(client.date().unwrap().year, 2024);
+}